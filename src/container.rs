@@ -1,9 +1,17 @@
+use std::cmp;
+use std::mem;
 use std::fmt::{ Debug, Formatter, Result };
+use std::ops::{ Deref, DerefMut };
+use std::slice;
+use std::sync::OnceLock;
+use std::vec;
 
-use num::traits::{ One };
+use num::traits::{ Zero, One, Bounded };
 
-use util::{ self, ExtInt, Halveable };
-use store::Store::{ self, Array, Bitmap };
+use util::{ self, ExtInt };
+use error::DeserializeError;
+use store::{ self, Store, StoreIter, IntoStoreIter };
+use store::Store::{ Array, Bitmap, Run };
 
 #[derive(PartialEq, Clone)]
 pub struct Container<Size: ExtInt> {
@@ -21,33 +29,150 @@ impl<Size: ExtInt> Container<Size> {
         }
     }
 }
-impl<Size: ExtInt + Halveable> Container<Size> {
-    pub fn from_raw64(bits: &Vec<u64>, next: usize) -> Container<<Size as util::Halveable>::HalfSize> {
-        let key = util::cast(bits[next]);
-        let len = bits[next+1];
-        let store_type = bits[next+2];
-        let size = bits[next+3];
-        if store_type == 0 {
-            let mut vs = Vec::new();
-            for i in next+4..next+4+(size as usize) {
-                vs.push(util::cast(bits[i]));
+impl<Size: ExtInt> Container<Size> {
+    fn from_store(key: Size, store: Store<Size>) -> Container<Size> {
+        let mut container = Container {
+            key: key,
+            len: store.len(),
+            store: store,
+        };
+        container.ensure_correct_store();
+        container
+    }
+
+    // Builds a container from strictly increasing indices, going straight to a bitmap when there
+    // are too many for an array.
+    pub fn from_sorted<I: ExactSizeIterator<Item = Size>>(key: Size, indices: I) -> Container<Size> {
+        let limit = util::cast(<Size as One>::one().rotate_right(4));
+        let store = if indices.len() as u64 <= limit {
+            Array(indices.collect())
+        } else {
+            let mut store = Bitmap(store::empty_bitmap::<Size>());
+            for index in indices {
+                store.push(index);
+            }
+            store
+        };
+        Container::from_store(key, store)
+    }
+
+    // Builds a container from a bit vector where bit `i` is index `i`, returning `None` if no bits
+    // are set.
+    pub fn from_words(key: Size, words: &[u64]) -> Option<Container<Size>> {
+        let limit = util::cast(<Size as One>::one().rotate_right(4));
+        let len = words.iter().map(|word| word.count_ones() as u64).sum::<u64>();
+        let store = if len == 0 {
+            return None;
+        } else if len <= limit {
+            let mut vec = Vec::with_capacity(len as usize);
+            for (index, &word) in words.iter().enumerate() {
+                let mut word = word;
+                while word != 0 {
+                    vec.push(util::cast(index as u64 * 64 + word.trailing_zeros() as u64));
+                    word &= word - 1;
+                }
             }
-            return Container{
-                key: key,
-                len: size,
-                store: Array(vs)
-            };
+            Array(vec)
         } else {
-            let mut vs = Vec::new();
-            for i in next+4..next+4+(size as usize) {
-                vs.push(bits[i]);
+            let mut bits = store::empty_bitmap::<Size>();
+            bits[..words.len()].copy_from_slice(words);
+            Bitmap(bits)
+        };
+        Some(Container::from_store(key, store))
+    }
+
+    // Unions containers sharing a key into one scratch store, going through a bitmap once they
+    // are too large to stay an array, and picks the final store type once at the end.
+    pub fn union_all(containers: &[&Container<Size>]) -> Container<Size> {
+        let key = containers[0].key;
+        let limit = util::cast(<Size as One>::one().rotate_right(4));
+        if containers.len() == 1 {
+            return containers[0].clone();
+        }
+        if containers.iter().map(|container| container.len).sum::<u64>() <= limit {
+            let mut store = containers[0].store.clone();
+            for container in &containers[1..] {
+                store.union_with(&container.store);
+            }
+            return Container::from_store(key, store);
+        }
+        let mut bits = store::empty_bitmap::<Size>();
+        for container in containers {
+            container.store.union_into(&mut bits);
+        }
+        Container::from_store(key, Bitmap(bits))
+    }
+
+    // Intersects containers sharing a key, smallest first, returning `None` as soon as the
+    // intersection becomes empty.
+    pub fn intersection_all(containers: &mut [&Container<Size>]) -> Option<Container<Size>> {
+        containers.sort_by_key(|container| container.len);
+        let mut store = containers[0].store.clone();
+        for container in &containers[1..] {
+            store.intersect_with(&container.store);
+            if store.is_empty() {
+                return None;
             }
-            return Container{
-                key: key,
-                len: len,
-                store: Bitmap(vs.into_boxed_slice())
-            };
         }
+        Some(Container::from_store(containers[0].key, store))
+    }
+
+    // Reads a container starting at `next`, returning it along with the index just past its end.
+    pub fn try_from_raw64(bits: &[u64], next: usize) -> ::std::result::Result<(Container<Size>, usize), DeserializeError> {
+        if bits.len() < next + 4 {
+            return Err(DeserializeError::UnexpectedEnd);
+        }
+        let max = util::cast::<Size, u64>(Bounded::max_value());
+        let (key, len, store_type, size) = (bits[next], bits[next+1], bits[next+2], bits[next+3]);
+        let end = match (next + 4).checked_add(size as usize) {
+            Some(end) if end <= bits.len() && size <= bits.len() as u64 => end,
+            _ => return Err(DeserializeError::UnexpectedEnd),
+        };
+        if key > max {
+            return Err(DeserializeError::ValueOutOfRange(key));
+        }
+        let vals = &bits[next+4..end];
+        let store = match store_type {
+            0 | 2 => {
+                if let Some(&value) = vals.iter().find(|&&value| value > max) {
+                    return Err(DeserializeError::ValueOutOfRange(value));
+                }
+                if store_type == 0 {
+                    Array(vals.iter().map(|&value| util::cast(value)).collect())
+                } else if size % 2 != 0 {
+                    return Err(DeserializeError::UnexpectedEnd);
+                } else {
+                    Run(vals.chunks(2).map(|run| (util::cast(run[0]), util::cast(run[1]))).collect())
+                }
+            },
+            1 => {
+                if size != store::capacity::<Size>() / 64 {
+                    return Err(DeserializeError::InvalidBitmapSize(size));
+                }
+                Bitmap(vals.to_vec().into_boxed_slice())
+            },
+            _ => return Err(DeserializeError::InvalidStoreType(store_type)),
+        };
+        Ok((Container::try_from_store(util::cast(key), len, store)?, end))
+    }
+
+    // Builds a container from deserialized parts, checking they are consistent.
+    pub fn try_from_store(key: Size, len: u64, store: Store<Size>) -> ::std::result::Result<Container<Size>, DeserializeError> {
+        store.validate()?;
+        let actual = store.len();
+        if actual == 0 {
+            return Err(DeserializeError::EmptyContainer);
+        }
+        if actual != len {
+            return Err(DeserializeError::InvalidCardinality { expected: len, actual: actual });
+        }
+        let mut container = Container {
+            key: key,
+            len: len,
+            store: store,
+        };
+        container.ensure_correct_store();
+        Ok(container)
     }
 }
 
@@ -58,43 +183,115 @@ impl<Size: ExtInt> Container<Size> {
     #[inline]
     pub fn len(&self) -> u64 { self.len }
 
+    #[inline]
+    pub fn store(&self) -> &Store<Size> { &self.store }
+
     #[inline]
     pub fn insert(&mut self, index: Size) -> bool {
         if self.store.insert(index) {
             self.len = self.len + 1;
-            self.ensure_correct_store();
+            self.ensure_correct_store_after_value();
             true
         } else {
             false
         }
     }
 
+    // Appends `index`, which must be greater than every value in the container.
+    #[inline]
+    pub fn push(&mut self, index: Size) {
+        self.store.push(index);
+        self.len = self.len + 1;
+        self.ensure_correct_store_after_value();
+    }
+
     #[inline]
     pub fn remove(&mut self, index: Size) -> bool {
         if self.store.remove(index) {
             self.len = self.len - 1;
-            self.ensure_correct_store();
+            self.ensure_correct_store_after_value();
             true
         } else {
             false
         }
     }
 
+    pub fn insert_range(&mut self, start: Size, end: Size) -> u64 {
+        let count = end.to64() - start.to64() + 1;
+        if count == store::capacity::<Size>() {
+            let inserted = count - self.len;
+            self.store = Run(vec![(start, end - start)]);
+            self.len = count;
+            return inserted;
+        }
+        let limit = util::cast(<Size as One>::one().rotate_right(4));
+        if let Array(..) = self.store {
+            // Avoid growing an array far past the point it would be converted anyway.
+            if self.len + count > limit {
+                self.store = self.store.to_run();
+            }
+        }
+        let inserted = self.store.insert_range(start, end);
+        self.len = self.len + inserted;
+        self.ensure_correct_store();
+        inserted
+    }
+
+    pub fn remove_range(&mut self, start: Size, end: Size) -> u64 {
+        let removed = self.store.remove_range(start, end);
+        self.len = self.len - removed;
+        self.ensure_correct_store();
+        removed
+    }
+
+    pub fn flip_range(&mut self, start: Size, end: Size) {
+        let count = end.to64() - start.to64() + 1;
+        let limit = util::cast(<Size as One>::one().rotate_right(4));
+        if let Array(..) = self.store {
+            // Go straight to a bitmap when the flipped values would not fit in an array.
+            let existing = self.range_len(start, end);
+            if self.len - existing + (count - existing) > limit {
+                self.store = self.store.to_bitmap();
+            }
+        }
+        let existing = self.store.flip_range(start, end);
+        self.len = self.len - existing + (count - existing);
+        self.ensure_correct_store();
+    }
+
+    // Moves the values not less than `index` into a new container with the same key.
+    pub fn split_off(&mut self, index: Size) -> Container<Size> {
+        let store = self.store.split_off(index);
+        self.len = self.store.len();
+        self.ensure_correct_store();
+        Container::from_store(self.key, store)
+    }
+
     #[inline]
     pub fn contains(&self, index: Size) -> bool {
         self.store.contains(index)
     }
 
     #[inline]
-    pub fn iter<'a>(&'a self) -> Box<Iterator<Item = Size> + 'a> {
+    pub fn iter<'a>(&'a self) -> StoreIter<'a, Size> {
         self.store.iter()
     }
 
+    #[inline]
+    pub fn into_iter(self) -> IntoStoreIter<Size> {
+        self.store.into_iter()
+    }
+
     #[inline]
     pub fn is_disjoint(&self, other: &Self) -> bool {
         self.store.is_disjoint(&other.store)
     }
 
+    #[inline]
+    pub fn intersection_len(&self, other: &Self) -> u64 {
+        self.store.intersection_len(&other.store)
+    }
+
     #[inline]
     pub fn is_subset(&self, other: &Self) -> bool {
         if self.len > other.len {
@@ -132,6 +329,46 @@ impl<Size: ExtInt> Container<Size> {
         self.ensure_correct_store();
     }
 
+    #[inline]
+    pub fn union(&self, other: &Self) -> Self {
+        Container::from_store(self.key, self.store.union(&other.store))
+    }
+
+    #[inline]
+    pub fn intersection(&self, other: &Self) -> Self {
+        Container::from_store(self.key, self.store.intersection(&other.store))
+    }
+
+    #[inline]
+    pub fn difference(&self, other: &Self) -> Self {
+        Container::from_store(self.key, self.store.difference(&other.store))
+    }
+
+    #[inline]
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        Container::from_store(self.key, self.store.symmetric_difference(&other.store))
+    }
+
+    #[inline]
+    pub fn union_owned(self, other: Self) -> Self {
+        Container::from_store(self.key, self.store.union_owned(other.store))
+    }
+
+    #[inline]
+    pub fn intersection_owned(self, other: Self) -> Self {
+        Container::from_store(self.key, self.store.intersection_owned(other.store))
+    }
+
+    #[inline]
+    pub fn difference_owned(self, other: Self) -> Self {
+        Container::from_store(self.key, self.store.difference_owned(other.store))
+    }
+
+    #[inline]
+    pub fn symmetric_difference_owned(self, other: Self) -> Self {
+        Container::from_store(self.key, self.store.symmetric_difference_owned(other.store))
+    }
+
     #[inline]
     pub fn min(&self) -> Size {
         self.store.min()
@@ -143,9 +380,77 @@ impl<Size: ExtInt> Container<Size> {
     }
 
     #[inline]
+    pub fn successor(&self, index: Size) -> Option<Size> {
+        self.store.successor(index)
+    }
+
+    #[inline]
+    pub fn predecessor(&self, index: Size) -> Option<Size> {
+        self.store.predecessor(index)
+    }
+
+    #[inline]
+    pub fn rank(&self, index: Size) -> u64 {
+        self.store.rank(index)
+    }
+
+    // Counts the values in the inclusive range `start...end`.
+    #[inline]
+    pub fn range_len(&self, start: Size, end: Size) -> u64 {
+        let before = if start == Zero::zero() { 0 } else { self.store.rank(start - One::one()) };
+        self.store.rank(end) - before
+    }
+
+    #[inline]
+    pub fn select(&self, n: u64) -> Option<Size> {
+        self.store.select(n)
+    }
+
+    pub fn run_optimize(&mut self) -> bool {
+        let size = mem::size_of::<Size>() as u64;
+        let run_size = self.store.run_count() * 2 * size;
+        let array_size = self.len * size;
+        let bitmap_size = store::capacity::<Size>() / 8;
+        let new_store = match self.store {
+            Run(..) if run_size < cmp::min(array_size, bitmap_size) => None,
+            ref store if run_size < cmp::min(array_size, bitmap_size) => Some(store.to_run()),
+            Array(..) if array_size <= bitmap_size => None,
+            ref store if array_size <= bitmap_size => Some(store.to_array()),
+            Bitmap(..) => None,
+            ref store => Some(store.to_bitmap()),
+        };
+        match new_store {
+            Some(new_store) => { self.store = new_store; true },
+            None => false,
+        }
+    }
+
+    pub fn remove_run_compression(&mut self) -> bool {
+        let limit = util::cast(<Size as One>::one().rotate_right(4));
+        let new_store = match self.store {
+            Run(..) if self.len <= limit => self.store.to_array(),
+            Run(..) => self.store.to_bitmap(),
+            _ => return false,
+        };
+        self.store = new_store;
+        true
+    }
+
+    #[inline]
+    // Picks the smallest store for the current values, switching to runs when they serialize
+    // smaller than both an array and a bitmap.
     fn ensure_correct_store(&mut self) {
         let limit = util::cast(<Size as One>::one().rotate_right(4));
+        let size = mem::size_of::<Size>() as u64;
+        let run_size = self.store.run_count() * 2 * size;
+        let array_size = self.len * size;
+        let bitmap_size = store::capacity::<Size>() / 8;
         let new_store = match (&self.store, self.len) {
+            // Only fall back to an array or bitmap once the runs take more space than it would.
+            (&Run(..), len) if len <= limit && run_size > array_size => Some(self.store.to_array()),
+            (&Run(..), len) if len > limit && run_size > bitmap_size => Some(self.store.to_bitmap()),
+            (&Run(..), _) => None,
+            (store, _) if run_size < cmp::min(array_size, bitmap_size) => Some(store.to_run()),
             (store @ &Bitmap(..), len) if len <= limit => Some(store.to_array()),
             (store @ &Array(..), len) if len > limit => Some(store.to_bitmap()),
             _ => None,
@@ -155,6 +460,18 @@ impl<Size: ExtInt> Container<Size> {
         }
     }
 
+    // Like `ensure_correct_store`, after a single value was added or removed. Counting the runs of
+    // an array or bitmap is linear in its size, so they are only reconsidered once they cross the
+    // array/bitmap limit.
+    fn ensure_correct_store_after_value(&mut self) {
+        let limit = util::cast(<Size as One>::one().rotate_right(4));
+        match (&self.store, self.len) {
+            (&Array(..), len) if len <= limit => (),
+            (&Bitmap(..), len) if len > limit => (),
+            _ => self.ensure_correct_store(),
+        }
+    }
+
     #[inline]
     pub fn to_raw64(&self) -> Vec<u64> {
         // key, num_entries, type, size_of_vals_block, vals
@@ -173,7 +490,15 @@ impl<Size: ExtInt> Container<Size> {
                 raw64.push(1);
                 raw64.push(bits.len() as u64);
                 raw64.extend_from_slice(bits);
-            }
+            },
+            Run(ref runs) => {
+                raw64.push(2);
+                raw64.push(runs.len() as u64 * 2);
+                for &(start, length) in runs {
+                    raw64.push(start.to64());
+                    raw64.push(length.to64());
+                }
+            },
         }
         return raw64;
     }
@@ -186,3 +511,82 @@ impl<Size: ExtInt + Debug> Debug for Container<Size> {
         format!("Container<{:?} @ {:?}>", self.len(), self.key()).fmt(formatter)
     }
 }
+
+// The sorted containers of a bitmap, along with the number of values before each one. The counts
+// are built on first use and dropped whenever the containers are borrowed mutably.
+#[derive(Clone)]
+pub struct Containers<Size: ExtInt> {
+    containers: Vec<Container<Size>>,
+    cardinalities: OnceLock<Vec<u64>>,
+}
+
+impl<Size: ExtInt> Containers<Size> {
+    pub fn new(containers: Vec<Container<Size>>) -> Containers<Size> {
+        Containers {
+            containers: containers,
+            cardinalities: OnceLock::new(),
+        }
+    }
+
+    // Returns how many values precede each container, followed by the total.
+    pub fn cardinalities(&self) -> &[u64] {
+        self.cardinalities.get_or_init(|| {
+            let mut total = 0;
+            let mut cardinalities = Vec::with_capacity(self.containers.len() + 1);
+            cardinalities.push(0);
+            for container in &self.containers {
+                total += container.len();
+                cardinalities.push(total);
+            }
+            cardinalities
+        })
+    }
+}
+
+impl<Size: ExtInt> Deref for Containers<Size> {
+    type Target = Vec<Container<Size>>;
+
+    fn deref(&self) -> &Vec<Container<Size>> {
+        &self.containers
+    }
+}
+
+impl<Size: ExtInt> DerefMut for Containers<Size> {
+    fn deref_mut(&mut self) -> &mut Vec<Container<Size>> {
+        self.cardinalities.take();
+        &mut self.containers
+    }
+}
+
+impl<Size: ExtInt> PartialEq for Containers<Size> {
+    fn eq(&self, other: &Self) -> bool {
+        self.containers == other.containers
+    }
+}
+
+impl<Size: ExtInt> IntoIterator for Containers<Size> {
+    type Item = Container<Size>;
+    type IntoIter = vec::IntoIter<Container<Size>>;
+
+    fn into_iter(self) -> vec::IntoIter<Container<Size>> {
+        self.containers.into_iter()
+    }
+}
+
+impl<'a, Size: ExtInt> IntoIterator for &'a Containers<Size> {
+    type Item = &'a Container<Size>;
+    type IntoIter = slice::Iter<'a, Container<Size>>;
+
+    fn into_iter(self) -> slice::Iter<'a, Container<Size>> {
+        self.containers.iter()
+    }
+}
+
+impl<'a, Size: ExtInt> IntoIterator for &'a mut Containers<Size> {
+    type Item = &'a mut Container<Size>;
+    type IntoIter = slice::IterMut<'a, Container<Size>>;
+
+    fn into_iter(self) -> slice::IterMut<'a, Container<Size>> {
+        self.deref_mut().iter_mut()
+    }
+}
@@ -0,0 +1,107 @@
+use std::io;
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+
+/// An error returned when deserializing a bitmap from invalid data.
+#[derive(Debug)]
+pub enum DeserializeError {
+    /// Reading the data failed.
+    Io(io::Error),
+    /// The data ended before the bitmap it describes did.
+    UnexpectedEnd,
+    /// The data does not start with a known format cookie.
+    InvalidCookie(u32),
+    /// There is more data after the end of the bitmap.
+    TrailingData,
+    /// A container has an unknown store type.
+    InvalidStoreType(u64),
+    /// Container keys are not strictly increasing.
+    UnsortedKeys,
+    /// A key or value does not fit in the bitmap's value type.
+    ValueOutOfRange(u64),
+    /// Values in an array container are not strictly increasing.
+    UnsortedValues,
+    /// A bitmap container does not have exactly enough words to cover its values.
+    InvalidBitmapSize(u64),
+    /// Runs in a run container are out of order, overlap or are adjacent.
+    InvalidRuns,
+    /// A container's recorded cardinality does not match its contents.
+    InvalidCardinality {
+        /// The cardinality recorded in the data.
+        expected: u64,
+        /// The number of values actually in the container.
+        actual: u64,
+    },
+    /// A container has no values.
+    EmptyContainer,
+}
+
+impl Display for DeserializeError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match *self {
+            DeserializeError::Io(ref error) => write!(formatter, "{}", error),
+            DeserializeError::UnexpectedEnd => write!(formatter, "unexpected end of data"),
+            DeserializeError::InvalidCookie(cookie) => write!(formatter, "invalid cookie {}", cookie),
+            DeserializeError::TrailingData => write!(formatter, "trailing data after bitmap"),
+            DeserializeError::InvalidStoreType(store_type) => write!(formatter, "invalid store type {}", store_type),
+            DeserializeError::UnsortedKeys => write!(formatter, "container keys are not sorted"),
+            DeserializeError::ValueOutOfRange(value) => write!(formatter, "value {} out of range", value),
+            DeserializeError::UnsortedValues => write!(formatter, "container values are not sorted"),
+            DeserializeError::InvalidBitmapSize(size) => write!(formatter, "invalid bitmap size {}", size),
+            DeserializeError::InvalidRuns => write!(formatter, "container runs are not sorted and disjoint"),
+            DeserializeError::InvalidCardinality { expected, actual } => {
+                write!(formatter, "container cardinality is {} but {} values were found", expected, actual)
+            },
+            DeserializeError::EmptyContainer => write!(formatter, "empty container"),
+        }
+    }
+}
+
+impl Error for DeserializeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            DeserializeError::Io(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DeserializeError {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => DeserializeError::UnexpectedEnd,
+            _ => DeserializeError::Io(error),
+        }
+    }
+}
+
+/// An error returned when appending values that are not strictly increasing, or that are not
+/// greater than the largest value already in the bitmap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonSortedIntegers {
+    /// The number of values that were appended before the first out of order one.
+    pub valid_until: u64,
+}
+
+impl Display for NonSortedIntegers {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "integers are not sorted after the first {} values", self.valid_until)
+    }
+}
+
+impl Error for NonSortedIntegers { }
+
+/// An error returned when a bit vector sets a value past the largest value the bitmap can hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfRange {
+    /// The first value set in the bit vector that the bitmap cannot hold.
+    pub value: u64,
+}
+
+impl Display for OutOfRange {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "value {} out of range", self.value)
+    }
+}
+
+impl Error for OutOfRange { }
@@ -1,19 +1,23 @@
 use std::iter::{ IntoIterator };
+use std::mem;
 use std::slice;
-use std::cmp::Ordering;
+use std::cmp::{ self, Ordering };
+use std::ops::RangeBounds;
 
-use num::traits::{ Zero, Bounded };
+use num::traits::{ Zero, One, Bounded };
 
-use iter::{ self, Iter, UnionIter, IntersectionIter, DifferenceIter, SymmetricDifferenceIter };
-use container::{ Container };
+use iter::{ self, Iter, RangeIter, UnionIter, IntersectionIter, DifferenceIter, SymmetricDifferenceIter };
+use container::{ Container, Containers };
+use error::{ DeserializeError, NonSortedIntegers, OutOfRange };
 use util::{ self, Halveable, ExtInt };
+use store;
 use store::Store::{ Array, Bitmap };
 
 use RoaringBitmap as RB;
 
 #[inline]
 pub fn new<Size: ExtInt + Halveable>() -> RB<Size> {
-    RB { containers: Vec::new() }
+    RB { containers: Containers::new(Vec::new()) }
 }
 
 pub fn insert<Size: ExtInt + Halveable>(this: &mut RB<Size>, value: Size) -> bool {
@@ -28,6 +32,39 @@ pub fn insert<Size: ExtInt + Halveable>(this: &mut RB<Size>, value: Size) -> boo
     container.insert(index)
 }
 
+// Appends `value`, which must be greater than every value in the bitmap, to the last container
+// without searching for it.
+fn push<Size: ExtInt + Halveable>(this: &mut RB<Size>, value: Size) {
+    let (key, index) = value.split();
+    match this.containers.last_mut() {
+        Some(container) if container.key() == key => return container.push(index),
+        _ => (),
+    }
+    let mut container = Container::new(key);
+    container.push(index);
+    this.containers.push(container);
+}
+
+pub fn extend_sorted<Size: ExtInt + Halveable, I: IntoIterator<Item = Size>>(this: &mut RB<Size>, iterator: I) -> Result<u64, NonSortedIntegers> {
+    let mut last = max(this);
+    let mut count = 0;
+    for value in iterator {
+        if last.map_or(false, |last| value <= last) {
+            return Err(NonSortedIntegers { valid_until: count });
+        }
+        push(this, value);
+        last = Some(value);
+        count += 1;
+    }
+    Ok(count)
+}
+
+pub fn from_sorted_iter<Size: ExtInt + Halveable, I: IntoIterator<Item = Size>>(iterator: I) -> Result<RB<Size>, NonSortedIntegers> {
+    let mut rb = new();
+    extend_sorted(&mut rb, iterator)?;
+    Ok(rb)
+}
+
 pub fn remove<Size: ExtInt + Halveable>(this: &mut RB<Size>, value: Size) -> bool {
     let (key, index) = value.split();
     match this.containers.binary_search_by(|container| container.key().cmp(&key)) {
@@ -45,6 +82,261 @@ pub fn remove<Size: ExtInt + Halveable>(this: &mut RB<Size>, value: Size) -> boo
     }
 }
 
+pub fn insert_range<Size: ExtInt + Halveable, R: RangeBounds<Size>>(this: &mut RB<Size>, range: R) -> u64 {
+    let (start, end) = match util::convert_range(range) {
+        Some(bounds) => bounds,
+        None => return 0,
+    };
+    let (start, end) = (start.split(), end.split());
+    let (first, last) = key_span(this, start.0, end.0);
+
+    // Rebuild the affected span in one pass rather than inserting new containers one at a time.
+    let mut existing = this.containers.drain(first..last).collect::<Vec<_>>().into_iter().peekable();
+    let mut containers = Vec::new();
+    let mut inserted = 0;
+    let mut key = start.0;
+    loop {
+        let mut container = match existing.peek() {
+            Some(container) if container.key() == key => existing.next().unwrap(),
+            _ => Container::new(key),
+        };
+        let (lo, hi) = index_bounds(key, start, end);
+        inserted += container.insert_range(lo, hi);
+        containers.push(container);
+        if key == end.0 {
+            break;
+        }
+        key = key + One::one();
+    }
+    this.containers.splice(first..first, containers);
+    inserted
+}
+
+pub fn remove_range<Size: ExtInt + Halveable, R: RangeBounds<Size>>(this: &mut RB<Size>, range: R) -> u64 {
+    let (start, end) = match util::convert_range(range) {
+        Some(bounds) => bounds,
+        None => return 0,
+    };
+    let (start, end) = (start.split(), end.split());
+    let (first, last) = key_span(this, start.0, end.0);
+
+    let mut removed = 0;
+    let mut kept = first;
+    for loc in first..last {
+        let key = this.containers[loc].key();
+        if key != start.0 && key != end.0 {
+            // Containers strictly inside the range are dropped whole.
+            removed += this.containers[loc].len();
+            continue;
+        }
+        let (lo, hi) = index_bounds(key, start, end);
+        removed += this.containers[loc].remove_range(lo, hi);
+        if this.containers[loc].len() != 0 {
+            this.containers.swap(kept, loc);
+            kept += 1;
+        }
+    }
+    this.containers.drain(kept..last);
+    removed
+}
+
+pub fn intersect_with_range<Size: ExtInt + Halveable, R: RangeBounds<Size>>(this: &mut RB<Size>, range: R) {
+    let (start, end) = match util::convert_range(range) {
+        Some(bounds) => bounds,
+        None => return this.containers.clear(),
+    };
+    let (start, end) = (start.split(), end.split());
+    let (first, last) = key_span(this, start.0, end.0);
+
+    this.containers.truncate(last);
+    this.containers.drain(..first);
+    // Interior containers are kept whole; only the boundary ones need trimming.
+    if let Some(container) = this.containers.first_mut() {
+        let (lo, hi) = index_bounds(container.key(), start, end);
+        retain_index_range(container, lo, hi);
+    }
+    if let Some(container) = this.containers.last_mut() {
+        let (lo, hi) = index_bounds(container.key(), start, end);
+        retain_index_range(container, lo, hi);
+    }
+    this.containers.retain(|container| container.len() != 0);
+}
+
+pub fn contains_range<Size: ExtInt + Halveable, R: RangeBounds<Size>>(this: &RB<Size>, range: R) -> bool {
+    let (start, end) = match util::convert_range(range) {
+        Some(bounds) => bounds,
+        None => return true,
+    };
+    let (start, end) = (start.split(), end.split());
+    let (first, last) = key_span(this, start.0, end.0);
+
+    // Every key in the range needs a container, interior ones full and boundary ones full
+    // across their part of the range.
+    if (last - first) as u64 != util::cast::<_, u64>(end.0) - util::cast::<_, u64>(start.0) + 1 {
+        return false;
+    }
+    this.containers[first..last].iter().all(|container| {
+        let (lo, hi) = index_bounds(container.key(), start, end);
+        container.range_len(lo, hi) == util::cast::<_, u64>(hi) - util::cast::<_, u64>(lo) + 1
+    })
+}
+
+pub fn intersects_range<Size: ExtInt + Halveable, R: RangeBounds<Size>>(this: &RB<Size>, range: R) -> bool {
+    let (start, end) = match util::convert_range(range) {
+        Some(bounds) => bounds,
+        None => return false,
+    };
+    let (start, end) = (start.split(), end.split());
+    let (first, last) = key_span(this, start.0, end.0);
+
+    this.containers[first..last].iter().any(|container| {
+        if container.key() != start.0 && container.key() != end.0 {
+            return true;
+        }
+        let (lo, hi) = index_bounds(container.key(), start, end);
+        container.range_len(lo, hi) != 0
+    })
+}
+
+pub fn range_cardinality<Size: ExtInt + Halveable, R: RangeBounds<Size>>(this: &RB<Size>, range: R) -> u64 {
+    let (start, end) = match util::convert_range(range) {
+        Some(bounds) => bounds,
+        None => return 0,
+    };
+    let (start, end) = (start.split(), end.split());
+    let (first, last) = key_span(this, start.0, end.0);
+
+    this.containers[first..last].iter()
+        .map(|container| {
+            if container.key() != start.0 && container.key() != end.0 {
+                return container.len();
+            }
+            let (lo, hi) = index_bounds(container.key(), start, end);
+            container.range_len(lo, hi)
+        })
+        .sum()
+}
+
+pub fn flip<Size: ExtInt + Halveable, R: RangeBounds<Size>>(this: &mut RB<Size>, range: R) {
+    if let Some((start, end)) = util::convert_range(range) {
+        flip_inclusive(this, start, end);
+    }
+}
+
+fn flip_inclusive<Size: ExtInt + Halveable>(this: &mut RB<Size>, start: Size, end: Size) {
+    let (start, end) = (start.split(), end.split());
+    let (first, last) = key_span(this, start.0, end.0);
+
+    let mut existing = this.containers.drain(first..last).collect::<Vec<_>>().into_iter().peekable();
+    let mut containers = Vec::new();
+    let mut key = start.0;
+    loop {
+        let (lo, hi) = index_bounds(key, start, end);
+        match existing.peek() {
+            Some(container) if container.key() == key => {
+                let mut container = existing.next().unwrap();
+                container.flip_range(lo, hi);
+                if container.len() != 0 {
+                    containers.push(container);
+                }
+            },
+            _ => {
+                // A missing container flips to exactly the range.
+                let mut container = Container::new(key);
+                container.insert_range(lo, hi);
+                containers.push(container);
+            },
+        }
+        if key == end.0 {
+            break;
+        }
+        key = key + One::one();
+    }
+    this.containers.splice(first..first, containers);
+}
+
+pub fn complement_in<Size: ExtInt + Halveable, R: RangeBounds<Size>>(this: &RB<Size>, range: R) -> RB<Size> {
+    let (start, end) = match util::convert_range(range) {
+        Some(bounds) => bounds,
+        None => return new(),
+    };
+    let (first, last) = key_span(this, start.split().0, end.split().0);
+
+    // Only the containers overlapping the range are copied, trimmed to it, and flipped.
+    let mut rb = RB { containers: Containers::new(this.containers[first..last].to_vec()) };
+    for container in &mut rb.containers {
+        let (lo, hi) = index_bounds(container.key(), start.split(), end.split());
+        retain_index_range(container, lo, hi);
+    }
+    rb.containers.retain(|container| container.len() != 0);
+    flip_inclusive(&mut rb, start, end);
+    rb
+}
+
+// Finds the containers whose keys fall within `start_key...end_key`, as a range of locations.
+fn key_span<Size: ExtInt + Halveable>(this: &RB<Size>, start_key: <Size as Halveable>::HalfSize, end_key: <Size as Halveable>::HalfSize) -> (usize, usize) {
+    let first = this.containers.binary_search_by(|container| container.key().cmp(&start_key)).unwrap_or_else(|loc| loc);
+    let last = this.containers.binary_search_by(|container| container.key().cmp(&end_key)).map(|loc| loc + 1).unwrap_or_else(|loc| loc);
+    (first, last)
+}
+
+// Clamps the split range `start...end` to the indices it covers in the container with `key`.
+fn index_bounds<Half: ExtInt>(key: Half, start: (Half, Half), end: (Half, Half)) -> (Half, Half) {
+    let lo = if key == start.0 { start.1 } else { Zero::zero() };
+    let hi = if key == end.0 { end.1 } else { Bounded::max_value() };
+    (lo, hi)
+}
+
+// Removes every value of `container` outside `lo...hi`.
+fn retain_index_range<Half: ExtInt>(container: &mut Container<Half>, lo: Half, hi: Half) {
+    if lo != Zero::zero() {
+        container.remove_range(Zero::zero(), lo - One::one());
+    }
+    if hi != Bounded::max_value() {
+        container.remove_range(hi + One::one(), Bounded::max_value());
+    }
+}
+
+pub fn split_off<Size: ExtInt + Halveable>(this: &mut RB<Size>, value: Size) -> RB<Size> {
+    let (key, index) = value.split();
+    let containers = match this.containers.binary_search_by(|container| container.key().cmp(&key)) {
+        Ok(loc) if index != Zero::zero() => {
+            // The boundary container is the only one that has to be cut.
+            let mut containers = this.containers.split_off(loc);
+            let upper = containers[0].split_off(index);
+            let lower = mem::replace(&mut containers[0], upper);
+            if lower.len() != 0 {
+                this.containers.push(lower);
+            }
+            if containers[0].len() == 0 {
+                containers.remove(0);
+            }
+            containers
+        },
+        Ok(loc) | Err(loc) => this.containers.split_off(loc),
+    };
+    RB { containers: Containers::new(containers) }
+}
+
+pub fn append<Size: ExtInt + Halveable>(this: &mut RB<Size>, other: &mut RB<Size>) {
+    let mut containers = mem::replace(&mut *other.containers, Vec::new());
+    let (first, last) = match (containers.first(), containers.last()) {
+        (Some(first), Some(last)) => (first.key(), last.key()),
+        _ => return,
+    };
+    // Bitmaps covering disjoint key ranges are joined by moving containers, anything else needs
+    // a merge.
+    if this.containers.last().map_or(true, |container| container.key() < first) {
+        this.containers.append(&mut containers);
+    } else if this.containers[0].key() > last {
+        containers.append(&mut this.containers);
+        *this.containers = containers;
+    } else {
+        let rb = mem::replace(this, new());
+        *this = union_owned(rb, RB { containers: Containers::new(containers) });
+    }
+}
+
 pub fn contains<Size: ExtInt + Halveable>(this: &RB<Size>, value: Size) -> bool {
     let (key, index) = value.split();
     match this.containers.binary_search_by(|container| container.key().cmp(&key)) {
@@ -63,11 +355,8 @@ pub fn is_empty<Size: ExtInt + Halveable>(this: &RB<Size>) -> bool {
     this.containers.is_empty()
 }
 
-pub fn len<Size: ExtInt + Halveable>(this: &RB<Size>) -> Size {
-    this.containers
-        .iter()
-        .map(|container| container.len())
-        .fold(Zero::zero(), |sum: Size, len| sum + util::cast(len))
+pub fn len<Size: ExtInt + Halveable>(this: &RB<Size>) -> u64 {
+    this.containers.iter().map(|container| container.len()).sum()
 }
 
 #[inline]
@@ -75,7 +364,19 @@ pub fn iter<'a, Size: ExtInt + Halveable>(this: &'a RB<Size>) -> Iter<'a, Size>
     iter::new(this.containers.iter())
 }
 
-fn pairs<'a, Size: ExtInt + Halveable>(this: &'a RB<Size>, other: &'a RB<Size>) -> Pairs<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+pub fn range<'a, Size: ExtInt + Halveable, R: RangeBounds<Size>>(this: &'a RB<Size>, range: R) -> RangeIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+    let (start, end) = match util::convert_range(range) {
+        Some(bounds) => bounds,
+        None => return iter::range::new(iter::new(this.containers[..0].iter())),
+    };
+    let (first, last) = key_span(this, start.split().0, end.split().0);
+    let mut inner = iter::new(this.containers[first..last].iter());
+    inner.advance_to(start);
+    inner.advance_back_to(end);
+    iter::range::new(inner)
+}
+
+pub fn pairs<'a, Size: ExtInt + Halveable>(this: &'a RB<Size>, other: &'a RB<Size>) -> Pairs<'a, Size> where <Size as Halveable>::HalfSize : 'a {
     Pairs::new(this.containers.iter(), other.containers.iter())
 }
 
@@ -85,6 +386,85 @@ pub fn is_disjoint<Size: ExtInt + Halveable>(this: &RB<Size>, other: &RB<Size>)
         .all(|(c1, c2)| c1.unwrap().is_disjoint(c2.unwrap()))
 }
 
+pub fn intersection_len<Size: ExtInt + Halveable>(this: &RB<Size>, other: &RB<Size>) -> u64 {
+    pairs(this, other)
+        .map(|pair| match pair {
+            (Some(c1), Some(c2)) => c1.intersection_len(c2),
+            _ => 0,
+        })
+        .sum()
+}
+
+pub fn union_len<Size: ExtInt + Halveable>(this: &RB<Size>, other: &RB<Size>) -> u64 {
+    pairs(this, other)
+        .map(|pair| match pair {
+            (Some(c1), Some(c2)) => c1.len() + c2.len() - c1.intersection_len(c2),
+            (Some(c), None) | (None, Some(c)) => c.len(),
+            (None, None) => 0,
+        })
+        .sum()
+}
+
+pub fn difference_len<Size: ExtInt + Halveable>(this: &RB<Size>, other: &RB<Size>) -> u64 {
+    pairs(this, other)
+        .map(|pair| match pair {
+            (Some(c1), Some(c2)) => c1.len() - c1.intersection_len(c2),
+            (Some(c1), None) => c1.len(),
+            _ => 0,
+        })
+        .sum()
+}
+
+pub fn symmetric_difference_len<Size: ExtInt + Halveable>(this: &RB<Size>, other: &RB<Size>) -> u64 {
+    pairs(this, other)
+        .map(|pair| match pair {
+            (Some(c1), Some(c2)) => c1.len() + c2.len() - 2 * c1.intersection_len(c2),
+            (Some(c), None) | (None, Some(c)) => c.len(),
+            (None, None) => 0,
+        })
+        .sum()
+}
+
+pub fn union_all<'a, Size: ExtInt + Halveable + 'a, I: IntoIterator<Item = &'a RB<Size>>>(bitmaps: I) -> RB<Size> {
+    let mut containers: Vec<_> = bitmaps.into_iter().flat_map(|bitmap| bitmap.containers.iter()).collect();
+    containers.sort_by(|c1, c2| c1.key().cmp(&c2.key()));
+
+    let mut result = new();
+    let mut start = 0;
+    while start < containers.len() {
+        let key = containers[start].key();
+        let end = start + containers[start..].iter().take_while(|container| container.key() == key).count();
+        result.containers.push(Container::union_all(&containers[start..end]));
+        start = end;
+    }
+    result
+}
+
+pub fn intersection_all<'a, Size: ExtInt + Halveable + 'a, I: IntoIterator<Item = &'a RB<Size>>>(bitmaps: I) -> RB<Size> {
+    let mut bitmaps: Vec<_> = bitmaps.into_iter().collect();
+    let mut result = new();
+    if bitmaps.is_empty() {
+        return result;
+    }
+    // Only keys present in the bitmap with the fewest containers can survive.
+    bitmaps.sort_by_key(|bitmap| bitmap.containers.len());
+    let mut group = Vec::with_capacity(bitmaps.len());
+    'keys: for container in &bitmaps[0].containers {
+        group.clear();
+        group.push(container);
+        for bitmap in &bitmaps[1..] {
+            match bitmap.containers.binary_search_by(|other| other.key().cmp(&container.key())) {
+                Ok(loc) => group.push(&bitmap.containers[loc]),
+                Err(_) => continue 'keys,
+            }
+        }
+        if let Some(container) = Container::intersection_all(&mut group) {
+            result.containers.push(container);
+        }
+    }
+    result
+}
+
 pub fn is_subset<Size: ExtInt + Halveable>(this: &RB<Size>, other: &RB<Size>) -> bool {
     for pair in pairs(this, other) {
         match pair {
@@ -128,22 +508,22 @@ pub fn is_superset<Size: ExtInt + Halveable>(this: &RB<Size>, other: &RB<Size>)
 
 #[inline]
 pub fn union<'a, Size: ExtInt + Halveable>(this: &'a RB<Size>, other: &'a RB<Size>) -> UnionIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
-    iter::union::new(this.iter(), other.iter())
+    iter::union::new(pairs(this, other))
 }
 
 #[inline]
 pub fn intersection<'a, Size: ExtInt + Halveable>(this: &'a RB<Size>, other: &'a RB<Size>) -> IntersectionIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
-    iter::intersection::new(this.iter(), other.iter())
+    iter::intersection::new(pairs(this, other))
 }
 
 #[inline]
 pub fn difference<'a, Size: ExtInt + Halveable>(this: &'a RB<Size>, other: &'a RB<Size>) -> DifferenceIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
-    iter::difference::new(this.iter(), other.iter())
+    iter::difference::new(pairs(this, other))
 }
 
 #[inline]
 pub fn symmetric_difference<'a, Size: ExtInt + Halveable>(this: &'a RB<Size>, other: &'a RB<Size>) -> SymmetricDifferenceIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
-    iter::symmetric_difference::new(this.iter(), other.iter())
+    iter::symmetric_difference::new(pairs(this, other))
 }
 
 #[inline]
@@ -211,6 +591,110 @@ pub fn symmetric_difference_with<Size: ExtInt + Halveable>(this: &mut RB<Size>,
     }
 }
 
+pub fn union_cloned<Size: ExtInt + Halveable>(this: &RB<Size>, other: &RB<Size>) -> RB<Size> {
+    merge_cloned(this, other, true, true, Container::union)
+}
+
+pub fn intersection_cloned<Size: ExtInt + Halveable>(this: &RB<Size>, other: &RB<Size>) -> RB<Size> {
+    merge_cloned(this, other, false, false, Container::intersection)
+}
+
+pub fn difference_cloned<Size: ExtInt + Halveable>(this: &RB<Size>, other: &RB<Size>) -> RB<Size> {
+    merge_cloned(this, other, true, false, Container::difference)
+}
+
+pub fn symmetric_difference_cloned<Size: ExtInt + Halveable>(this: &RB<Size>, other: &RB<Size>) -> RB<Size> {
+    merge_cloned(this, other, true, true, Container::symmetric_difference)
+}
+
+// Builds a new bitmap from two borrowed ones, cloning the containers found on only one side when
+// `keep1` or `keep2` is set and combining those sharing a key with `op`.
+fn merge_cloned<Size, F>(this: &RB<Size>, other: &RB<Size>, keep1: bool, keep2: bool, op: F) -> RB<Size>
+    where Size: ExtInt + Halveable,
+          F: Fn(&Container<<Size as Halveable>::HalfSize>, &Container<<Size as Halveable>::HalfSize>) -> Container<<Size as Halveable>::HalfSize>
+{
+    let containers = pairs(this, other)
+        .filter_map(|pair| match pair {
+            (Some(c1), Some(c2)) => {
+                let container = op(c1, c2);
+                if container.len() != 0 { Some(container) } else { None }
+            },
+            (Some(c1), None) if keep1 => Some(c1.clone()),
+            (None, Some(c2)) if keep2 => Some(c2.clone()),
+            _ => None,
+        })
+        .collect();
+    RB { containers: Containers::new(containers) }
+}
+
+pub fn union_owned<Size: ExtInt + Halveable>(this: RB<Size>, other: RB<Size>) -> RB<Size> {
+    merge_owned(this, other, true, true, Container::union_owned)
+}
+
+pub fn intersection_owned<Size: ExtInt + Halveable>(this: RB<Size>, other: RB<Size>) -> RB<Size> {
+    merge_owned(this, other, false, false, Container::intersection_owned)
+}
+
+pub fn difference_owned<Size: ExtInt + Halveable>(this: RB<Size>, other: RB<Size>) -> RB<Size> {
+    merge_owned(this, other, true, false, Container::difference_owned)
+}
+
+pub fn symmetric_difference_owned<Size: ExtInt + Halveable>(this: RB<Size>, other: RB<Size>) -> RB<Size> {
+    merge_owned(this, other, true, true, Container::symmetric_difference_owned)
+}
+
+// Walks the containers of both bitmaps in key order, moving those found on only one side into
+// the result when `keep1` or `keep2` is set and combining those sharing a key with `op`.
+fn merge_owned<Size, F>(this: RB<Size>, other: RB<Size>, keep1: bool, keep2: bool, op: F) -> RB<Size>
+    where Size: ExtInt + Halveable,
+          F: Fn(Container<<Size as Halveable>::HalfSize>, Container<<Size as Halveable>::HalfSize>) -> Container<<Size as Halveable>::HalfSize>
+{
+    let mut containers = Vec::with_capacity(cmp::max(this.containers.len(), other.containers.len()));
+    let mut iter1 = this.containers.into_iter().peekable();
+    let mut iter2 = other.containers.into_iter().peekable();
+    loop {
+        let ordering = match (iter1.peek(), iter2.peek()) {
+            (Some(c1), Some(c2)) => c1.key().cmp(&c2.key()),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => break,
+        };
+        match ordering {
+            Ordering::Less => {
+                let container = iter1.next().unwrap();
+                if keep1 {
+                    containers.push(container);
+                } else if iter2.peek().is_none() {
+                    break;
+                }
+            },
+            Ordering::Greater => {
+                let container = iter2.next().unwrap();
+                if keep2 {
+                    containers.push(container);
+                } else if iter1.peek().is_none() {
+                    break;
+                }
+            },
+            Ordering::Equal => {
+                let container = op(iter1.next().unwrap(), iter2.next().unwrap());
+                if container.len() != 0 {
+                    containers.push(container);
+                }
+            },
+        }
+    }
+    RB { containers: Containers::new(containers) }
+}
+
+pub fn run_optimize<Size: ExtInt + Halveable>(this: &mut RB<Size>) -> bool {
+    this.containers.iter_mut().map(|container| container.run_optimize()).fold(false, |changed, c| changed || c)
+}
+
+pub fn remove_run_compression<Size: ExtInt + Halveable>(this: &mut RB<Size>) -> bool {
+    this.containers.iter_mut().map(|container| container.remove_run_compression()).fold(false, |changed, c| changed || c)
+}
+
 #[inline]
 pub fn to_raw64<Size: ExtInt + Halveable>(this: &RB<Size>) -> Vec<u64> {
     let mut raw64: Vec<u64> = Vec::new();
@@ -221,18 +705,76 @@ pub fn to_raw64<Size: ExtInt + Halveable>(this: &RB<Size>) -> Vec<u64> {
     return raw64
 }
 
-#[inline]
-pub fn from_raw64<Size: ExtInt + Halveable>(bits: Vec<u64>) -> RB<Size>{
+pub fn try_from_raw64<Size: ExtInt + Halveable>(bits: &[u64]) -> Result<RB<Size>, DeserializeError> {
+    match try_from_raw64_at(bits, 0) {
+        Ok((_, end)) if end != bits.len() => Err(DeserializeError::TrailingData),
+        result => result.map(|(rb, _)| rb),
+    }
+}
+
+// Reads a bitmap starting at `start`, returning it along with the index just past its end.
+pub fn try_from_raw64_at<Size: ExtInt + Halveable>(bits: &[u64], start: usize) -> Result<(RB<Size>, usize), DeserializeError> {
     let mut rb: RB<Size> = RB::new();
 
-    let num_containers = bits[0];
-    let mut next = 1;
+    let num_containers = match bits.get(start) {
+        Some(&num_containers) if num_containers <= bits.len() as u64 => num_containers,
+        _ => return Err(DeserializeError::UnexpectedEnd),
+    };
+    let mut next = start + 1;
     for _ in 0..num_containers {
-        let size = bits[next+3];
-        rb.containers.push(Container::<Size>::from_raw64(&bits, next));
-        next += 4 + size as usize;
+        let (container, end) = Container::try_from_raw64(bits, next)?;
+        if rb.containers.last().map_or(false, |last| last.key() >= container.key()) {
+            return Err(DeserializeError::UnsortedKeys);
+        }
+        rb.containers.push(container);
+        next = end;
+    }
+    Ok((rb, next))
+}
+
+pub fn from_sorted_slice<Size: ExtInt + Halveable>(values: &[Size]) -> Result<RB<Size>, NonSortedIntegers> {
+    if let Some(loc) = values.windows(2).position(|pair| pair[0] >= pair[1]) {
+        return Err(NonSortedIntegers { valid_until: loc as u64 + 1 });
+    }
+    let mut rb = new();
+    let mut rest = values;
+    while let Some(&first) = rest.first() {
+        let key = first.split().0;
+        let end = rest.binary_search_by(|value| value.split().0.cmp(&key).then(Ordering::Less)).unwrap_err();
+        let (chunk, tail) = rest.split_at(end);
+        rb.containers.push(Container::from_sorted(key, chunk.iter().map(|value| value.split().1)));
+        rest = tail;
+    }
+    Ok(rb)
+}
+
+pub fn from_bitset_words<Size: ExtInt + Halveable>(words: &[u64]) -> Result<RB<Size>, OutOfRange> {
+    let max = util::cast::<Size, u64>(Bounded::max_value());
+    // Words past the range of `Size` are allowed as long as none of their bits are set.
+    let len = cmp::min(words.len() as u64, max / 64 + 1) as usize;
+    if let Some((index, &word)) = words[len..].iter().enumerate().find(|&(_, &word)| word != 0) {
+        return Err(OutOfRange { value: (len + index) as u64 * 64 + word.trailing_zeros() as u64 });
     }
-    return rb;
+    let container_words = (store::capacity::<<Size as Halveable>::HalfSize>() / 64) as usize;
+    let containers = words[..len].chunks(container_words).enumerate()
+        .filter_map(|(key, words)| Container::from_words(util::cast(key as u64), words))
+        .collect();
+    Ok(RB { containers: Containers::new(containers) })
+}
+
+pub fn to_bitset_words<Size: ExtInt + Halveable>(this: &RB<Size>) -> Vec<u64> {
+    let len = match max(this) {
+        Some(max) => (max.to64() / 64 + 1) as usize,
+        None => return Vec::new(),
+    };
+    let container_words = (store::capacity::<<Size as Halveable>::HalfSize>() / 64) as usize;
+    let mut words = vec![0; len];
+    for container in &this.containers {
+        let start = util::cast::<_, u64>(container.key()) as usize * container_words;
+        let end = cmp::min(start + container_words, len);
+        container.store().union_into(&mut words[start..end]);
+    }
+    words
 }
 
 #[inline]
@@ -251,33 +793,97 @@ pub fn from_iter_ref<'a, Size: ExtInt + Halveable + 'a, I: IntoIterator<Item = &
 
 #[inline]
 pub fn extend<Size: ExtInt + Halveable, I: IntoIterator<Item = Size>>(this: &mut RB<Size>, iterator: I) {
+    // Values past the current maximum are appended directly, so sorted input never searches.
+    let mut last = max(this);
     for value in iterator {
-        this.insert(value);
+        if last.map_or(true, |last| value > last) {
+            push(this, value);
+            last = Some(value);
+        } else {
+            this.insert(value);
+        }
     }
 }
 
 #[inline]
 pub fn extend_ref<'a, Size: ExtInt + Halveable + 'a, I: IntoIterator<Item = &'a Size>>(this: &mut RB<Size>, iterator: I) {
-    for value in iterator {
-        this.insert(*value);
+    let mut last = max(this);
+    for &value in iterator {
+        if last.map_or(true, |last| value > last) {
+            push(this, value);
+            last = Some(value);
+        } else {
+            this.insert(value);
+        }
     }
 }
 
-pub fn min<Size: ExtInt + Halveable>(this: &RB<Size>) -> Size {
-    match this.containers.first() {
-        Some(ref head) => Halveable::join(head.key(), head.min()),
-        None => Bounded::min_value(),
-    }
+pub fn rank<Size: ExtInt + Halveable>(this: &RB<Size>, value: Size) -> u64 {
+    let (key, index) = value.split();
+    let (before, container) = match this.containers.binary_search_by(|container| container.key().cmp(&key)) {
+        Ok(loc) => (loc, Some(&this.containers[loc])),
+        Err(loc) => (loc, None),
+    };
+    this.containers.cardinalities()[before] + container.map_or(0, |container| container.rank(index))
+}
+
+pub fn select<Size: ExtInt + Halveable>(this: &RB<Size>, n: u64) -> Option<Size> {
+    let cardinalities = this.containers.cardinalities();
+    // Containers are never empty, so the counts are strictly increasing and the `n`th integer
+    // lives in the last container preceded by at most `n` integers.
+    let loc = match cardinalities.binary_search(&n) {
+        Ok(loc) => loc,
+        Err(loc) => loc - 1,
+    };
+    this.containers.get(loc).and_then(|container| {
+        container.select(n - cardinalities[loc]).map(|index| Halveable::join(container.key(), index))
+    })
+}
+
+pub fn min<Size: ExtInt + Halveable>(this: &RB<Size>) -> Option<Size> {
+    this.containers.first().map(|head| Halveable::join(head.key(), head.min()))
+}
+
+pub fn max<Size: ExtInt + Halveable>(this: &RB<Size>) -> Option<Size> {
+    this.containers.last().map(|tail| Halveable::join(tail.key(), tail.max()))
+}
+
+pub fn successor<Size: ExtInt + Halveable>(this: &RB<Size>, value: Size) -> Option<Size> {
+    let (key, index) = value.split();
+    let next = match this.containers.binary_search_by(|container| container.key().cmp(&key)) {
+        Ok(loc) => {
+            let container = &this.containers[loc];
+            if let Some(index) = container.successor(index) {
+                return Some(Halveable::join(key, index));
+            }
+            loc + 1
+        },
+        Err(loc) => loc,
+    };
+    this.containers.get(next).map(|container| Halveable::join(container.key(), container.min()))
 }
 
-pub fn max<Size: ExtInt + Halveable>(this: &RB<Size>) -> Size {
-    match this.containers.last() {
-        Some(ref tail) => Halveable::join(tail.key(), tail.max()),
-        None => Bounded::max_value(),
+pub fn predecessor<Size: ExtInt + Halveable>(this: &RB<Size>, value: Size) -> Option<Size> {
+    let (key, index) = value.split();
+    let prev = match this.containers.binary_search_by(|container| container.key().cmp(&key)) {
+        Ok(loc) => {
+            let container = &this.containers[loc];
+            if let Some(index) = container.predecessor(index) {
+                return Some(Halveable::join(key, index));
+            }
+            loc
+        },
+        Err(loc) => loc,
+    };
+    if prev == 0 {
+        None
+    } else {
+        let container = &this.containers[prev - 1];
+        Some(Halveable::join(container.key(), container.max()))
     }
 }
 
-struct Pairs<'a, Size: ExtInt + Halveable + 'a> where <Size as Halveable>::HalfSize : 'a {
+pub struct Pairs<'a, Size: ExtInt + Halveable + 'a> where <Size as Halveable>::HalfSize : 'a {
     iter1: slice::Iter<'a, Container<<Size as Halveable>::HalfSize>>,
     iter2: slice::Iter<'a, Container<<Size as Halveable>::HalfSize>>,
     current1: Option<&'a Container<<Size as Halveable>::HalfSize>>,
@@ -296,6 +902,23 @@ impl<'a, Size: ExtInt + Halveable> Pairs<'a, Size> {
     }
 }
 
+impl<'a, Size: ExtInt + Halveable> Pairs<'a, Size> {
+    // Skips both sides forward to the first container with a key not less than `key`.
+    pub fn advance_to(&mut self, key: <Size as Halveable>::HalfSize) {
+        advance_side(&mut self.current1, &mut self.iter1, key);
+        advance_side(&mut self.current2, &mut self.iter2, key);
+    }
+}
+
+fn advance_side<'a, Size: ExtInt>(current: &mut Option<&'a Container<Size>>, iter: &mut slice::Iter<'a, Container<Size>>, key: Size) {
+    if current.map_or(false, |container| container.key() < key) {
+        let containers = iter.as_slice();
+        let loc = containers.binary_search_by(|container| container.key().cmp(&key)).unwrap_or_else(|loc| loc);
+        *iter = containers[loc..].iter();
+        *current = iter.next();
+    }
+}
+
 impl<'a, Size: ExtInt + Halveable> Iterator for Pairs<'a, Size> {
     type Item = (Option<&'a Container<<Size as Halveable>::HalfSize>>, Option<&'a Container<<Size as Halveable>::HalfSize>>);
 
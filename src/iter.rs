@@ -1,37 +1,163 @@
 use std::slice;
+use std::vec;
 
-use util::{ Either, ExtInt, Halveable };
-use util::Either::{ Left, Right };
+use util::{ ExtInt, Halveable };
 use container::{ Container };
+use store::{ StoreIter, IntoStoreIter };
+use imp::Pairs;
 
 /// An iterator for `RoaringBitmap`.
 pub struct Iter<'a, Size: ExtInt + Halveable + 'a> where <Size as Halveable>::HalfSize : 'a {
-    inner_iter: Option<(<Size as Halveable>::HalfSize, Box<Iterator<Item = <Size as Halveable>::HalfSize> + 'a>)>,
+    inner_iter: Option<(<Size as Halveable>::HalfSize, StoreIter<'a, <Size as Halveable>::HalfSize>)>,
+    inner_iter_back: Option<(<Size as Halveable>::HalfSize, StoreIter<'a, <Size as Halveable>::HalfSize>)>,
     container_iter: slice::Iter<'a, Container<<Size as Halveable>::HalfSize>>,
 }
 
 #[inline]
-fn next_iter<'a, Size: ExtInt + Halveable + 'a>(container_iter: &mut slice::Iter<'a, Container<<Size as Halveable>::HalfSize>>) -> Option<(<Size as Halveable>::HalfSize, Box<Iterator<Item = <Size as Halveable>::HalfSize> + 'a>)> {
-    container_iter.next().map(|container| (container.key(), container.iter()))
-}
-
-#[inline]
-pub fn new<'a, Size: ExtInt + Halveable + 'a>(mut container_iter: slice::Iter<'a, Container<<Size as Halveable>::HalfSize>>) -> Iter<'a, Size> {
+pub fn new<'a, Size: ExtInt + Halveable + 'a>(container_iter: slice::Iter<'a, Container<<Size as Halveable>::HalfSize>>) -> Iter<'a, Size> {
     Iter {
-        inner_iter: next_iter::<'a, Size>(&mut container_iter),
+        inner_iter: None,
+        inner_iter_back: None,
         container_iter: container_iter
     }
 }
 
 impl<'a, Size: ExtInt + Halveable + 'a> Iter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
-    #[inline]
-    fn choose_next(&mut self) -> Option<Either<Size, Option<(<Size as Halveable>::HalfSize, Box<Iterator<Item = <Size as Halveable>::HalfSize> + 'a>)>>> {
-        match self.inner_iter {
-            Some((key, ref mut iter)) => Some(match iter.next() {
-                Some(value) => Left(Halveable::join(key, value)),
-                None => Right(next_iter::<'a, Size>(&mut self.container_iter)),
-            }),
-            None => None,
+    /// Advances the iterator so that the next value it returns is the first remaining value
+    /// greater than or equal to `target`. Does nothing if the iterator is already past `target`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = vec![1, 5, 100000, 100010].into_iter().collect();
+    /// let mut iter = rb.iter();
+    ///
+    /// iter.advance_to(6);
+    /// assert_eq!(iter.next(), Some(100000));
+    /// iter.advance_to(3);
+    /// assert_eq!(iter.next(), Some(100010));
+    /// ```
+    pub fn advance_to(&mut self, target: Size) {
+        let (key, index) = target.split();
+        if let Some((front_key, ref mut iter)) = self.inner_iter {
+            if front_key == key {
+                iter.advance_to(index);
+                return;
+            } else if front_key > key {
+                return;
+            }
+        }
+        let containers = self.container_iter.as_slice();
+        let loc = containers.binary_search_by(|container| container.key().cmp(&key)).unwrap_or_else(|loc| loc);
+        self.container_iter = containers[loc..].iter();
+        self.inner_iter = None;
+        match self.container_iter.as_slice().first() {
+            Some(container) if container.key() == key => {
+                let mut iter = container.iter();
+                iter.advance_to(index);
+                self.container_iter.next();
+                self.inner_iter = Some((key, iter));
+            },
+            Some(_) => (),
+            None => {
+                // Only the container the back cursor is part way through is left.
+                match self.inner_iter_back {
+                    Some((back_key, _)) if back_key < key => self.inner_iter_back = None,
+                    Some((back_key, ref mut iter)) if back_key == key => iter.advance_to(index),
+                    _ => (),
+                }
+            },
+        }
+    }
+
+    /// Fills `out` with the next values of the iterator, returning how many were written. This
+    /// is only less than `out.len()` once the iterator runs out of values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = vec![1, 5, 100000, 100010].into_iter().collect();
+    /// let mut iter = rb.iter();
+    /// let mut buffer = [0; 3];
+    ///
+    /// assert_eq!(iter.next_many(&mut buffer), 3);
+    /// assert_eq!(buffer, [1, 5, 100000]);
+    /// assert_eq!(iter.next_many(&mut buffer), 1);
+    /// assert_eq!(buffer[0], 100010);
+    /// ```
+    pub fn next_many(&mut self, out: &mut [Size]) -> usize {
+        let mut written = 0;
+        while written < out.len() {
+            if let Some((key, ref mut iter)) = self.inner_iter {
+                written += iter.next_many(&mut out[written..], |value| Halveable::join(key, value));
+                if written == out.len() {
+                    break;
+                }
+            }
+            match self.container_iter.next() {
+                Some(container) => self.inner_iter = Some((container.key(), container.iter())),
+                None => {
+                    self.inner_iter = None;
+                    if let Some((key, ref mut iter)) = self.inner_iter_back {
+                        written += iter.next_many(&mut out[written..], |value| Halveable::join(key, value));
+                    }
+                    break;
+                },
+            }
+        }
+        written
+    }
+
+    /// Advances the back of the iterator so that the next value `next_back` returns is the last
+    /// remaining value less than or equal to `target`. Does nothing if the back of the iterator
+    /// is already before `target`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = vec![1, 5, 100000, 100010].into_iter().collect();
+    /// let mut iter = rb.iter();
+    ///
+    /// iter.advance_back_to(99999);
+    /// assert_eq!(iter.next_back(), Some(5));
+    /// assert_eq!(iter.next(), Some(1));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn advance_back_to(&mut self, target: Size) {
+        let (key, index) = target.split();
+        if let Some((back_key, ref mut iter)) = self.inner_iter_back {
+            if back_key == key {
+                iter.advance_back_to(index);
+                return;
+            } else if back_key < key {
+                return;
+            }
+        }
+        let containers = self.container_iter.as_slice();
+        let loc = containers.binary_search_by(|container| container.key().cmp(&key)).map(|loc| loc + 1).unwrap_or_else(|loc| loc);
+        self.container_iter = containers[..loc].iter();
+        self.inner_iter_back = None;
+        match self.container_iter.as_slice().last() {
+            Some(container) if container.key() == key => {
+                let mut iter = container.iter();
+                iter.advance_back_to(index);
+                self.container_iter.next_back();
+                self.inner_iter_back = Some((key, iter));
+            },
+            Some(_) => (),
+            None => {
+                match self.inner_iter {
+                    Some((front_key, _)) if front_key > key => self.inner_iter = None,
+                    Some((front_key, ref mut iter)) if front_key == key => iter.advance_back_to(index),
+                    _ => (),
+                }
+            },
         }
     }
 }
@@ -40,190 +166,450 @@ impl<'a, Size: ExtInt + Halveable + 'a> Iterator for Iter<'a, Size> where <Size
     type Item = Size;
 
     fn next(&mut self) -> Option<Size> {
-        match self.choose_next() {
-            None => None,
-            Some(Left(val)) => Some(val),
-            Some(Right(new_iter)) => {
-                self.inner_iter = new_iter;
-                self.next()
-            },
+        loop {
+            if let Some((key, ref mut iter)) = self.inner_iter {
+                if let Some(value) = iter.next() {
+                    return Some(Halveable::join(key, value));
+                }
+            }
+            match self.container_iter.next() {
+                Some(container) => self.inner_iter = Some((container.key(), container.iter())),
+                None => {
+                    // The only values left are in the container the back cursor is part way through.
+                    self.inner_iter = None;
+                    return match self.inner_iter_back {
+                        Some((key, ref mut iter)) => iter.next().map(|value| Halveable::join(key, value)),
+                        None => None,
+                    };
+                },
+            }
         }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let next = self.container_iter.clone().map(|container| container.len() as usize).fold(0, |acc, len| acc + len);
-        match self.inner_iter {
-            Some((_, ref iter)) => match iter.size_hint() {
-                (min, max) => (next + min, max.map(|m| next + m)),
-            },
-            None => (next, Some(next)),
+        let next = self.container_iter.clone().map(|container| container.len() as usize).sum::<usize>();
+        let current = self.inner_iter.as_ref().map_or(0, |&(_, ref iter)| iter.len());
+        let current_back = self.inner_iter_back.as_ref().map_or(0, |&(_, ref iter)| iter.len());
+        let len = next + current + current_back;
+        (len, Some(len))
+    }
+}
+
+impl<'a, Size: ExtInt + Halveable + 'a> DoubleEndedIterator for Iter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+    fn next_back(&mut self) -> Option<Size> {
+        loop {
+            if let Some((key, ref mut iter)) = self.inner_iter_back {
+                if let Some(value) = iter.next_back() {
+                    return Some(Halveable::join(key, value));
+                }
+            }
+            match self.container_iter.next_back() {
+                Some(container) => self.inner_iter_back = Some((container.key(), container.iter())),
+                None => {
+                    self.inner_iter_back = None;
+                    return match self.inner_iter {
+                        Some((key, ref mut iter)) => iter.next_back().map(|value| Halveable::join(key, value)),
+                        None => None,
+                    };
+                },
+            }
+        }
+    }
+}
+
+impl<'a, Size: ExtInt + Halveable + 'a> ExactSizeIterator for Iter<'a, Size> where <Size as Halveable>::HalfSize : 'a { }
+
+/// An owning iterator for `RoaringBitmap`.
+pub struct IntoIter<Size: ExtInt + Halveable> {
+    inner_iter: Option<(<Size as Halveable>::HalfSize, IntoStoreIter<<Size as Halveable>::HalfSize>)>,
+    inner_iter_back: Option<(<Size as Halveable>::HalfSize, IntoStoreIter<<Size as Halveable>::HalfSize>)>,
+    container_iter: vec::IntoIter<Container<<Size as Halveable>::HalfSize>>,
+}
+
+pub mod into_iter {
+    use std::vec;
+
+    use util::{ ExtInt, Halveable };
+    use container::Container;
+    use super::IntoIter;
+
+    #[inline]
+    pub fn new<Size: ExtInt + Halveable>(container_iter: vec::IntoIter<Container<<Size as Halveable>::HalfSize>>) -> IntoIter<Size> {
+        IntoIter {
+            inner_iter: None,
+            inner_iter_back: None,
+            container_iter: container_iter,
+        }
+    }
+}
+
+impl<Size: ExtInt + Halveable> Iterator for IntoIter<Size> {
+    type Item = Size;
+
+    fn next(&mut self) -> Option<Size> {
+        loop {
+            if let Some((key, ref mut iter)) = self.inner_iter {
+                if let Some(value) = iter.next() {
+                    return Some(Halveable::join(key, value));
+                }
+            }
+            match self.container_iter.next() {
+                Some(container) => self.inner_iter = Some((container.key(), container.into_iter())),
+                None => {
+                    self.inner_iter = None;
+                    return match self.inner_iter_back {
+                        Some((key, ref mut iter)) => iter.next().map(|value| Halveable::join(key, value)),
+                        None => None,
+                    };
+                },
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let next = self.container_iter.as_slice().iter().map(|container| container.len() as usize).sum::<usize>();
+        let current = self.inner_iter.as_ref().map_or(0, |&(_, ref iter)| iter.len());
+        let current_back = self.inner_iter_back.as_ref().map_or(0, |&(_, ref iter)| iter.len());
+        let len = next + current + current_back;
+        (len, Some(len))
+    }
+}
+
+impl<Size: ExtInt + Halveable> DoubleEndedIterator for IntoIter<Size> {
+    fn next_back(&mut self) -> Option<Size> {
+        loop {
+            if let Some((key, ref mut iter)) = self.inner_iter_back {
+                if let Some(value) = iter.next_back() {
+                    return Some(Halveable::join(key, value));
+                }
+            }
+            match self.container_iter.next_back() {
+                Some(container) => self.inner_iter_back = Some((container.key(), container.into_iter())),
+                None => {
+                    self.inner_iter_back = None;
+                    return match self.inner_iter {
+                        Some((key, ref mut iter)) => iter.next_back().map(|value| Halveable::join(key, value)),
+                        None => None,
+                    };
+                },
+            }
+        }
+    }
+}
+
+impl<Size: ExtInt + Halveable> ExactSizeIterator for IntoIter<Size> { }
+
+/// An iterator over the values of a `RoaringBitmap` that lie in a range.
+pub struct RangeIter<'a, Size: ExtInt + Halveable + 'a> where <Size as Halveable>::HalfSize : 'a {
+    inner: Iter<'a, Size>,
+}
+
+pub mod range {
+    use util::{ ExtInt, Halveable };
+    use super::{ Iter, RangeIter };
+
+    #[inline]
+    pub fn new<'a, Size: ExtInt + Halveable + 'a>(inner: Iter<'a, Size>) -> RangeIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+        RangeIter { inner: inner }
+    }
+}
+
+impl<'a, Size: ExtInt + Halveable + 'a> Iterator for RangeIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+    type Item = Size;
+
+    #[inline]
+    fn next(&mut self) -> Option<Size> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, Size: ExtInt + Halveable + 'a> DoubleEndedIterator for RangeIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+    #[inline]
+    fn next_back(&mut self) -> Option<Size> {
+        self.inner.next_back()
+    }
+}
+
+impl<'a, Size: ExtInt + Halveable + 'a> ExactSizeIterator for RangeIter<'a, Size> where <Size as Halveable>::HalfSize : 'a { }
+
+// The values of one container pair, either borrowed from a container that passes through
+// unchanged or computed from an overlapping pair.
+enum Values<'a, Size: ExtInt + 'a> {
+    Borrowed(StoreIter<'a, Size>),
+    Owned(IntoStoreIter<Size>),
+}
+
+impl<'a, Size: ExtInt> Values<'a, Size> {
+    fn advance_to(&mut self, index: Size) {
+        match *self {
+            Values::Borrowed(ref mut iter) => iter.advance_to(index),
+            Values::Owned(ref mut iter) => iter.advance_to(index),
+        }
+    }
+}
+
+impl<'a, Size: ExtInt> Iterator for Values<'a, Size> {
+    type Item = Size;
+
+    #[inline]
+    fn next(&mut self) -> Option<Size> {
+        match *self {
+            Values::Borrowed(ref mut iter) => iter.next(),
+            Values::Owned(ref mut iter) => iter.next(),
+        }
+    }
+}
+
+type PairOp<'a, Size> = fn(Option<&'a Container<Size>>, Option<&'a Container<Size>>) -> Option<Values<'a, Size>>;
+
+// Walks the container pairs of two bitmaps, yielding the values `op` produces for each pair. A
+// pair `op` returns `None` for is skipped without looking at its values.
+struct PairsIter<'a, Size: ExtInt + Halveable + 'a> where <Size as Halveable>::HalfSize : 'a {
+    pairs: Pairs<'a, Size>,
+    current: Option<(<Size as Halveable>::HalfSize, Values<'a, <Size as Halveable>::HalfSize>)>,
+    op: PairOp<'a, <Size as Halveable>::HalfSize>,
+}
+
+impl<'a, Size: ExtInt + Halveable + 'a> PairsIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+    #[inline]
+    fn new(pairs: Pairs<'a, Size>, op: PairOp<'a, <Size as Halveable>::HalfSize>) -> PairsIter<'a, Size> {
+        PairsIter { pairs: pairs, current: None, op: op }
+    }
+
+    fn advance_to(&mut self, target: Size) {
+        let (key, index) = target.split();
+        if let Some((current_key, ref mut values)) = self.current {
+            if current_key == key {
+                values.advance_to(index);
+                return;
+            } else if current_key > key {
+                return;
+            }
+        }
+        self.pairs.advance_to(key);
+        loop {
+            match self.pairs.next() {
+                None => {
+                    self.current = None;
+                    return;
+                },
+                Some((c1, c2)) => {
+                    let pair_key = c1.or(c2).unwrap().key();
+                    if let Some(mut values) = (self.op)(c1, c2) {
+                        if pair_key == key {
+                            values.advance_to(index);
+                        }
+                        self.current = Some((pair_key, values));
+                        return;
+                    }
+                },
+            }
+        }
+    }
+
+    fn next(&mut self) -> Option<Size> {
+        loop {
+            if let Some((key, ref mut values)) = self.current {
+                if let Some(value) = values.next() {
+                    return Some(Halveable::join(key, value));
+                }
+            }
+            match self.pairs.next() {
+                None => {
+                    self.current = None;
+                    return None;
+                },
+                Some((c1, c2)) => {
+                    let key = c1.or(c2).unwrap().key();
+                    self.current = (self.op)(c1, c2).map(|values| (key, values));
+                },
+            }
         }
     }
 }
 
+// Iterates over the values of a container computed from an overlapping pair.
+#[inline]
+fn owned<'a, Size: ExtInt + 'a>(container: Container<Size>) -> Option<Values<'a, Size>> {
+    if container.len() == 0 {
+        None
+    } else {
+        Some(Values::Owned(container.into_iter()))
+    }
+}
+
 /// An iterator for `RoaringBitmap`.
 pub struct UnionIter<'a, Size: ExtInt + Halveable + 'a> where <Size as Halveable>::HalfSize : 'a {
-    current1: Option<Size>,
-    current2: Option<Size>,
-    iter1: Iter<'a, Size>,
-    iter2: Iter<'a, Size>,
+    inner: PairsIter<'a, Size>,
 }
 
 pub mod union {
     use util::{ ExtInt, Halveable };
-    use super::{ Iter, UnionIter };
+    use container::Container;
+    use imp::Pairs;
+    use super::{ owned, PairsIter, UnionIter, Values };
 
-    #[inline]
-    pub fn new<'a, Size: ExtInt + Halveable + 'a>(mut iter1: Iter<'a, Size>, mut iter2: Iter<'a, Size>) -> UnionIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
-        UnionIter {
-            current1: iter1.next(),
-            current2: iter2.next(),
-            iter1: iter1,
-            iter2: iter2,
+    fn op<'a, Size: ExtInt>(c1: Option<&'a Container<Size>>, c2: Option<&'a Container<Size>>) -> Option<Values<'a, Size>> {
+        match (c1, c2) {
+            (Some(c1), Some(c2)) => owned(c1.union(c2)),
+            (Some(c), None) | (None, Some(c)) => Some(Values::Borrowed(c.iter())),
+            (None, None) => None,
         }
     }
+
+    #[inline]
+    pub fn new<'a, Size: ExtInt + Halveable + 'a>(pairs: Pairs<'a, Size>) -> UnionIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+        UnionIter { inner: PairsIter::new(pairs, op) }
+    }
+}
+
+impl<'a, Size: ExtInt + Halveable + 'a> UnionIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+    /// Advances the iterator so that the next value it returns is the first remaining value
+    /// greater than or equal to `target`, skipping whole containers where it can.
+    #[inline]
+    pub fn advance_to(&mut self, target: Size) {
+        self.inner.advance_to(target)
+    }
 }
 
 impl<'a, Size: ExtInt + Halveable + 'a> Iterator for UnionIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
     type Item = Size;
 
+    #[inline]
     fn next(&mut self) -> Option<Size> {
-        match (self.current1, self.current2) {
-            (None, None) => None,
-            (val, None) => { self.current1 = self.iter1.next(); val },
-            (None, val) => { self.current2 = self.iter2.next(); val },
-            (val1, val2) if val1 < val2 => { self.current1 = self.iter1.next(); val1 },
-            (val1, val2) if val1 > val2 => { self.current2 = self.iter2.next(); val2 },
-            (val1, val2) if val1 == val2 => {
-                self.current1 = self.iter1.next();
-                self.current2 = self.iter2.next();
-                val1
-            },
-            _ => panic!("Should not be possible to get here"),
-        }
+        self.inner.next()
     }
 }
 
 /// An iterator for `RoaringBitmap`.
 pub struct IntersectionIter<'a, Size: ExtInt + Halveable + 'a> where <Size as Halveable>::HalfSize : 'a {
-    current1: Option<Size>,
-    current2: Option<Size>,
-    iter1: Iter<'a, Size>,
-    iter2: Iter<'a, Size>,
+    inner: PairsIter<'a, Size>,
 }
 
 pub mod intersection {
     use util::{ ExtInt, Halveable };
-    use super::{ Iter, IntersectionIter };
+    use container::Container;
+    use imp::Pairs;
+    use super::{ owned, PairsIter, IntersectionIter, Values };
 
-    #[inline]
-    pub fn new<'a, Size: ExtInt + Halveable + 'a>(mut iter1: Iter<'a, Size>, mut iter2: Iter<'a, Size>) -> IntersectionIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
-        IntersectionIter {
-            current1: iter1.next(),
-            current2: iter2.next(),
-            iter1: iter1,
-            iter2: iter2,
+    fn op<'a, Size: ExtInt>(c1: Option<&'a Container<Size>>, c2: Option<&'a Container<Size>>) -> Option<Values<'a, Size>> {
+        match (c1, c2) {
+            (Some(c1), Some(c2)) => owned(c1.intersection(c2)),
+            _ => None,
         }
     }
+
+    #[inline]
+    pub fn new<'a, Size: ExtInt + Halveable + 'a>(pairs: Pairs<'a, Size>) -> IntersectionIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+        IntersectionIter { inner: PairsIter::new(pairs, op) }
+    }
+}
+
+impl<'a, Size: ExtInt + Halveable + 'a> IntersectionIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+    /// Advances the iterator so that the next value it returns is the first remaining value
+    /// greater than or equal to `target`, skipping whole containers where it can.
+    #[inline]
+    pub fn advance_to(&mut self, target: Size) {
+        self.inner.advance_to(target)
+    }
 }
 
 impl<'a, Size: ExtInt + Halveable + 'a> Iterator for IntersectionIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
     type Item = Size;
 
+    #[inline]
     fn next(&mut self) -> Option<Size> {
-        match (self.current1, self.current2) {
-            (None, _) | (_, None) => None,
-            (val1, val2) if val1 < val2 => { self.current1 = self.iter1.next(); self.next() },
-            (val1, val2) if val1 > val2 => { self.current2 = self.iter2.next(); self.next() },
-            (val1, val2) if val1 == val2 => {
-                self.current1 = self.iter1.next();
-                self.current2 = self.iter2.next();
-                val1
-            },
-            _ => panic!("Should not be possible to get here"),
-        }
+        self.inner.next()
     }
 }
 
 /// An iterator for `RoaringBitmap`.
 pub struct DifferenceIter<'a, Size: ExtInt + Halveable + 'a> where <Size as Halveable>::HalfSize : 'a {
-    current1: Option<Size>,
-    current2: Option<Size>,
-    iter1: Iter<'a, Size>,
-    iter2: Iter<'a, Size>,
+    inner: PairsIter<'a, Size>,
 }
 
 pub mod difference {
     use util::{ ExtInt, Halveable };
-    use super::{ Iter, DifferenceIter };
+    use container::Container;
+    use imp::Pairs;
+    use super::{ owned, PairsIter, DifferenceIter, Values };
 
-    #[inline]
-    pub fn new<'a, Size: ExtInt + Halveable + 'a>(mut iter1: Iter<'a, Size>, mut iter2: Iter<'a, Size>) -> DifferenceIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
-        DifferenceIter {
-            current1: iter1.next(),
-            current2: iter2.next(),
-            iter1: iter1,
-            iter2: iter2,
+    fn op<'a, Size: ExtInt>(c1: Option<&'a Container<Size>>, c2: Option<&'a Container<Size>>) -> Option<Values<'a, Size>> {
+        match (c1, c2) {
+            (Some(c1), Some(c2)) => owned(c1.difference(c2)),
+            (Some(c1), None) => Some(Values::Borrowed(c1.iter())),
+            _ => None,
         }
     }
+
+    #[inline]
+    pub fn new<'a, Size: ExtInt + Halveable + 'a>(pairs: Pairs<'a, Size>) -> DifferenceIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+        DifferenceIter { inner: PairsIter::new(pairs, op) }
+    }
+}
+
+impl<'a, Size: ExtInt + Halveable + 'a> DifferenceIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+    /// Advances the iterator so that the next value it returns is the first remaining value
+    /// greater than or equal to `target`, skipping whole containers where it can.
+    #[inline]
+    pub fn advance_to(&mut self, target: Size) {
+        self.inner.advance_to(target)
+    }
 }
 
 impl<'a, Size: ExtInt + Halveable + 'a> Iterator for DifferenceIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
     type Item = Size;
 
+    #[inline]
     fn next(&mut self) -> Option<Size> {
-        loop {
-            match (self.current1, self.current2) {
-                (None, _) | (_, None) => return None,
-                (val1, val2) if val1 < val2 => { self.current1 = self.iter1.next(); return val1; },
-                (val1, val2) if val1 > val2 => self.current2 = self.iter2.next(),
-                (val1, val2) if val1 == val2 => {
-                    self.current1 = self.iter1.next();
-                    self.current2 = self.iter2.next();
-                },
-                _ => panic!("Should not be possible to get here"),
-            }
-        }
+        self.inner.next()
     }
 }
 
 /// An iterator for `RoaringBitmap`.
 pub struct SymmetricDifferenceIter<'a, Size: ExtInt + Halveable + 'a> where <Size as Halveable>::HalfSize : 'a {
-    current1: Option<Size>,
-    current2: Option<Size>,
-    iter1: Iter<'a, Size>,
-    iter2: Iter<'a, Size>,
+    inner: PairsIter<'a, Size>,
 }
 
 pub mod symmetric_difference {
     use util::{ ExtInt, Halveable };
-    use super::{ Iter, SymmetricDifferenceIter };
+    use container::Container;
+    use imp::Pairs;
+    use super::{ owned, PairsIter, SymmetricDifferenceIter, Values };
 
-    #[inline]
-    pub fn new<'a, Size: ExtInt + Halveable + 'a>(mut iter1: Iter<'a, Size>, mut iter2: Iter<'a, Size>) -> SymmetricDifferenceIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
-        SymmetricDifferenceIter {
-            current1: iter1.next(),
-            current2: iter2.next(),
-            iter1: iter1,
-            iter2: iter2,
+    fn op<'a, Size: ExtInt>(c1: Option<&'a Container<Size>>, c2: Option<&'a Container<Size>>) -> Option<Values<'a, Size>> {
+        match (c1, c2) {
+            (Some(c1), Some(c2)) => owned(c1.symmetric_difference(c2)),
+            (Some(c), None) | (None, Some(c)) => Some(Values::Borrowed(c.iter())),
+            (None, None) => None,
         }
     }
+
+    #[inline]
+    pub fn new<'a, Size: ExtInt + Halveable + 'a>(pairs: Pairs<'a, Size>) -> SymmetricDifferenceIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+        SymmetricDifferenceIter { inner: PairsIter::new(pairs, op) }
+    }
+}
+
+impl<'a, Size: ExtInt + Halveable + 'a> SymmetricDifferenceIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+    /// Advances the iterator so that the next value it returns is the first remaining value
+    /// greater than or equal to `target`, skipping whole containers where it can.
+    #[inline]
+    pub fn advance_to(&mut self, target: Size) {
+        self.inner.advance_to(target)
+    }
 }
 
 impl<'a, Size: ExtInt + Halveable + 'a> Iterator for SymmetricDifferenceIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
     type Item = Size;
 
+    #[inline]
     fn next(&mut self) -> Option<Size> {
-        match (self.current1, self.current2) {
-            (None, _) | (_, None) => None,
-            (val1, val2) if val1 < val2 => { self.current1 = self.iter1.next(); val1 },
-            (val1, val2) if val1 > val2 => { self.current2 = self.iter2.next(); val2 },
-            (val1, val2) if val1 == val2 => {
-                self.current1 = self.iter1.next();
-                self.current2 = self.iter2.next();
-                self.next()
-            },
-            _ => panic!("Should not be possible to get here"),
-        }
+        self.inner.next()
     }
 }
@@ -12,19 +12,28 @@
 
 extern crate num;
 
+use std::io;
+use std::mem;
 use std::fmt::{ Debug, Formatter, Result };
-use std::ops::{ BitXor, BitAnd, BitOr, Sub };
-use std::iter::{ IntoIterator, FromIterator };
+use std::ops::{ BitXor, BitAnd, BitOr, Sub, BitXorAssign, BitAndAssign, BitOrAssign, SubAssign, RangeBounds };
+use std::iter::{ IntoIterator, FromIterator, Rev };
+
+use num::traits::Zero;
 
 use util::{ Halveable, ExtInt };
 
-pub use iter::{ Iter, UnionIter, IntersectionIter, DifferenceIter, SymmetricDifferenceIter };
+pub use iter::{ Iter, IntoIter, RangeIter, UnionIter, IntersectionIter, DifferenceIter, SymmetricDifferenceIter };
+pub use treemap::{ RoaringTreemap, TreemapIter, TreemapIntoIter, TreemapUnionIter, TreemapIntersectionIter, TreemapDifferenceIter, TreemapSymmetricDifferenceIter };
+pub use error::{ DeserializeError, NonSortedIntegers, OutOfRange };
 
 mod imp;
 mod util;
 mod iter;
 mod store;
 mod container;
+mod treemap;
+mod error;
+mod serialization;
 
 /// A compressed bitmap using the [Roaring bitmap compression scheme](http://roaringbitmap.org).
 ///
@@ -44,7 +53,7 @@ mod container;
 /// ```
 #[derive(PartialEq, Clone)]
 pub struct RoaringBitmap<Size: ExtInt + Halveable> where <Size as Halveable>::HalfSize: ExtInt {
-    containers: Vec<container::Container<<Size as Halveable>::HalfSize>>,
+    containers: container::Containers<<Size as Halveable>::HalfSize>,
 }
 
 impl<Size: ExtInt + Halveable> RoaringBitmap<Size> {
@@ -61,9 +70,144 @@ impl<Size: ExtInt + Halveable> RoaringBitmap<Size> {
         imp::new()
     }
 
+    /// Creates a `RoaringBitmap` from the output of `to_raw64`.
+    ///
+    /// # Panics
+    ///
+    /// If `v` is not a valid serialized bitmap, see `try_from_raw64` for a non-panicking version.
     #[inline]
     pub fn from_raw64(v: Vec<u64>) -> Self {
-        imp::from_raw64(v)
+        match imp::try_from_raw64(&v) {
+            Ok(rb) => rb,
+            Err(error) => panic!("invalid raw64 bitmap: {}", error),
+        }
+    }
+
+    /// Creates a `RoaringBitmap` from the output of `to_raw64`, checking that `bits` is complete
+    /// and describes a valid bitmap.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::{ RoaringBitmap, DeserializeError };
+    ///
+    /// let rb: RoaringBitmap<u32> = (1..4u32).collect();
+    /// let mut raw64 = rb.to_raw64();
+    ///
+    /// assert_eq!(RoaringBitmap::try_from_raw64(&raw64).unwrap(), rb);
+    ///
+    /// raw64.pop();
+    /// match RoaringBitmap::<u32>::try_from_raw64(&raw64) {
+    ///     Err(DeserializeError::UnexpectedEnd) => (),
+    ///     _ => panic!(),
+    /// }
+    /// ```
+    #[inline]
+    pub fn try_from_raw64(bits: &[u64]) -> std::result::Result<Self, DeserializeError> {
+        imp::try_from_raw64(bits)
+    }
+
+    /// Creates a bitmap from an iterator of strictly increasing values, filling each container
+    /// in turn rather than searching for where every value belongs.
+    ///
+    /// Returns an error if the values are not strictly increasing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::{ RoaringBitmap, NonSortedIntegers };
+    ///
+    /// let rb: RoaringBitmap<u32> = RoaringBitmap::from_sorted_iter(0..10).unwrap();
+    /// assert_eq!(rb, (0..10).collect());
+    ///
+    /// assert_eq!(RoaringBitmap::<u32>::from_sorted_iter(vec![1, 3, 2]), Err(NonSortedIntegers { valid_until: 2 }));
+    /// ```
+    #[inline]
+    pub fn from_sorted_iter<I: IntoIterator<Item = Size>>(iterator: I) -> std::result::Result<Self, NonSortedIntegers> {
+        imp::from_sorted_iter(iterator)
+    }
+
+    /// Creates a bitmap from a slice of strictly increasing values, cutting it into containers
+    /// and building each one directly.
+    ///
+    /// Returns an error if the values are not strictly increasing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::{ RoaringBitmap, NonSortedIntegers };
+    ///
+    /// let rb = RoaringBitmap::from_sorted_slice(&[1u32, 5, 70000, 140000]).unwrap();
+    /// assert_eq!(rb.iter().collect::<Vec<u32>>(), vec![1, 5, 70000, 140000]);
+    ///
+    /// assert_eq!(RoaringBitmap::from_sorted_slice(&[1u32, 5, 5]), Err(NonSortedIntegers { valid_until: 2 }));
+    /// ```
+    #[inline]
+    pub fn from_sorted_slice(values: &[Size]) -> std::result::Result<Self, NonSortedIntegers> {
+        imp::from_sorted_slice(values)
+    }
+
+    /// Creates a bitmap from a bit vector, where bit `i` of the vector (bit `i % 64` of word
+    /// `i / 64`) being set means the value `i` is in the set.
+    ///
+    /// Returns an error holding the first value set past the range of `Size`, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::{ RoaringBitmap, OutOfRange };
+    ///
+    /// let rb: RoaringBitmap<u32> = RoaringBitmap::from_bitset_words(&[0b1010, 0, 1]).unwrap();
+    /// assert_eq!(rb.iter().collect::<Vec<u32>>(), vec![1, 3, 128]);
+    ///
+    /// let mut words = vec![0; 1025];
+    /// words[1024] = 0b100;
+    /// assert_eq!(RoaringBitmap::<u16>::from_bitset_words(&words), Err(OutOfRange { value: 65538 }));
+    /// ```
+    #[inline]
+    pub fn from_bitset_words(words: &[u64]) -> std::result::Result<Self, OutOfRange> {
+        imp::from_bitset_words(words)
+    }
+
+    /// Returns the set as a bit vector, where bit `i` of the vector (bit `i % 64` of word
+    /// `i / 64`) is set if the value `i` is in the set. The vector ends at the word holding the
+    /// largest value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = vec![1, 3, 128].into_iter().collect();
+    /// assert_eq!(rb.to_bitset_words(), vec![0b1010, 0, 1]);
+    /// assert_eq!(RoaringBitmap::from_bitset_words(&rb.to_bitset_words()), Ok(rb));
+    /// ```
+    #[inline]
+    pub fn to_bitset_words(&self) -> Vec<u64> {
+        imp::to_bitset_words(self)
+    }
+
+    /// Appends strictly increasing values that are all greater than the current maximum of the
+    /// set, returning how many were added.
+    ///
+    /// Stops at the first value that is out of order and returns an error recording how many
+    /// values were appended before it; those values stay in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::{ RoaringBitmap, NonSortedIntegers };
+    ///
+    /// let mut rb: RoaringBitmap<u32> = (0..10).collect();
+    /// assert_eq!(rb.extend_sorted(10..20), Ok(10));
+    /// assert_eq!(rb.len(), 20);
+    ///
+    /// assert_eq!(rb.extend_sorted(vec![20, 21, 5, 22]), Err(NonSortedIntegers { valid_until: 2 }));
+    /// assert_eq!(rb.max(), Some(21));
+    /// ```
+    #[inline]
+    pub fn extend_sorted<I: IntoIterator<Item = Size>>(&mut self, iterator: I) -> std::result::Result<u64, NonSortedIntegers> {
+        imp::extend_sorted(self, iterator)
     }
 
     /// Adds a value to the set. Returns `true` if the value was not already present in the set.
@@ -101,6 +245,180 @@ impl<Size: ExtInt + Halveable> RoaringBitmap<Size> {
         imp::remove(self, value)
     }
 
+    /// Inserts every value in `range` into the set. Returns the number of values that were not
+    /// already present.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb: RoaringBitmap<u32> = RoaringBitmap::new();
+    /// rb.insert(5);
+    /// assert_eq!(rb.insert_range(2..10), 7);
+    /// assert_eq!(rb.insert_range(2..10), 0);
+    /// assert_eq!(rb.contains(9), true);
+    /// assert_eq!(rb.contains(10), false);
+    /// ```
+    #[inline]
+    pub fn insert_range<R: RangeBounds<Size>>(&mut self, range: R) -> u64 {
+        imp::insert_range(self, range)
+    }
+
+    /// Removes every value in `range` from the set. Returns the number of values that were
+    /// present.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb: RoaringBitmap<u32> = RoaringBitmap::new();
+    /// rb.insert_range(2..10);
+    /// assert_eq!(rb.remove_range(5..=20), 5);
+    /// assert_eq!(rb.remove_range(5..=20), 0);
+    /// assert_eq!(rb.contains(4), true);
+    /// assert_eq!(rb.contains(5), false);
+    /// ```
+    #[inline]
+    pub fn remove_range<R: RangeBounds<Size>>(&mut self, range: R) -> u64 {
+        imp::remove_range(self, range)
+    }
+
+    /// Removes every value outside of `range` from the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb: RoaringBitmap<u32> = (0..100000).collect();
+    /// rb.intersect_with_range(500..70000);
+    /// assert_eq!(rb, (500..70000).collect());
+    /// ```
+    #[inline]
+    pub fn intersect_with_range<R: RangeBounds<Size>>(&mut self, range: R) {
+        imp::intersect_with_range(self, range)
+    }
+
+    /// Returns `true` if every value in `range` is in the set. An empty range is always
+    /// contained.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = (10..100000).collect();
+    /// assert_eq!(rb.contains_range(10..70000), true);
+    /// assert_eq!(rb.contains_range(9..70000), false);
+    /// assert_eq!(rb.contains_range(5..5), true);
+    /// ```
+    #[inline]
+    pub fn contains_range<R: RangeBounds<Size>>(&self, range: R) -> bool {
+        imp::contains_range(self, range)
+    }
+
+    /// Returns `true` if any value in `range` is in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = vec![10, 70000].into_iter().collect();
+    /// assert_eq!(rb.intersects_range(0..=10), true);
+    /// assert_eq!(rb.intersects_range(11..70000), false);
+    /// ```
+    #[inline]
+    pub fn intersects_range<R: RangeBounds<Size>>(&self, range: R) -> bool {
+        imp::intersects_range(self, range)
+    }
+
+    /// Returns the number of values of the set that are in `range`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = (0..200000).filter(|i| i % 2 == 0).collect();
+    /// assert_eq!(rb.range_cardinality(10..20), 5);
+    /// assert_eq!(rb.range_cardinality(..), 100000);
+    /// ```
+    #[inline]
+    pub fn range_cardinality<R: RangeBounds<Size>>(&self, range: R) -> u64 {
+        imp::range_cardinality(self, range)
+    }
+
+    /// Flips every value in `range`, removing those in the set and inserting those that are not.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb: RoaringBitmap<u32> = vec![1, 3, 10].into_iter().collect();
+    /// rb.flip(0..5);
+    /// assert_eq!(rb.iter().collect::<Vec<u32>>(), vec![0, 2, 4, 10]);
+    /// ```
+    #[inline]
+    pub fn flip<R: RangeBounds<Size>>(&mut self, range: R) {
+        imp::flip(self, range)
+    }
+
+    /// Returns the values in `range` that are not in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = vec![1, 3, 10].into_iter().collect();
+    /// let complement = rb.complement_in(0..5);
+    /// assert_eq!(complement.iter().collect::<Vec<u32>>(), vec![0, 2, 4]);
+    /// ```
+    #[inline]
+    pub fn complement_in<R: RangeBounds<Size>>(&self, range: R) -> Self {
+        imp::complement_in(self, range)
+    }
+
+    /// Splits the set in two at `value`, returning everything greater than or equal to it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb: RoaringBitmap<u32> = (0..100000).collect();
+    /// let upper = rb.split_off(&70000);
+    /// assert_eq!(rb, (0..70000).collect());
+    /// assert_eq!(upper, (70000..100000).collect());
+    /// ```
+    #[inline]
+    pub fn split_off(&mut self, value: &Size) -> Self {
+        imp::split_off(self, *value)
+    }
+
+    /// Moves all values from `other` into this set, leaving `other` empty. This is cheapest when
+    /// every value of one set is smaller than every value of the other.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb1: RoaringBitmap<u32> = (0..70000).collect();
+    /// let mut rb2: RoaringBitmap<u32> = (70000..100000).collect();
+    /// rb1.append(&mut rb2);
+    /// assert_eq!(rb1, (0..100000).collect());
+    /// assert_eq!(rb2.is_empty(), true);
+    /// ```
+    #[inline]
+    pub fn append(&mut self, other: &mut Self) {
+        imp::append(self, other)
+    }
+
     /// Returns `true` if this set contains the specified integer.
     ///
     /// # Examples
@@ -155,7 +473,8 @@ impl<Size: ExtInt + Halveable> RoaringBitmap<Size> {
         imp::is_empty(self)
     }
 
-    /// Returns the number of distinct integers added to the set.
+    /// Returns the number of distinct integers added to the set. This is a `u64` so that it can
+    /// count every value of `Size`, which would overflow `Size` itself.
     ///
     /// # Examples
     ///
@@ -173,10 +492,120 @@ impl<Size: ExtInt + Halveable> RoaringBitmap<Size> {
     /// assert_eq!(rb.len(), 2);
     /// ```
     #[inline]
-    pub fn len(&self) -> Size {
+    pub fn len(&self) -> u64 {
         imp::len(self)
     }
 
+    /// Returns the smallest integer in the set, or `None` if the set is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb: RoaringBitmap<u32> = RoaringBitmap::new();
+    /// assert_eq!(rb.min(), None);
+    ///
+    /// rb.insert(3);
+    /// rb.insert(100000);
+    /// assert_eq!(rb.min(), Some(3));
+    /// ```
+    #[inline]
+    pub fn min(&self) -> Option<Size> {
+        imp::min(self)
+    }
+
+    /// Returns the largest integer in the set, or `None` if the set is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb: RoaringBitmap<u32> = RoaringBitmap::new();
+    /// assert_eq!(rb.max(), None);
+    ///
+    /// rb.insert(3);
+    /// rb.insert(100000);
+    /// assert_eq!(rb.max(), Some(100000));
+    /// ```
+    #[inline]
+    pub fn max(&self) -> Option<Size> {
+        imp::max(self)
+    }
+
+    /// Returns the smallest integer in the set that is strictly greater than `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = vec![1, 5, 100000].into_iter().collect();
+    /// assert_eq!(rb.successor(0), Some(1));
+    /// assert_eq!(rb.successor(1), Some(5));
+    /// assert_eq!(rb.successor(5), Some(100000));
+    /// assert_eq!(rb.successor(100000), None);
+    /// ```
+    #[inline]
+    pub fn successor(&self, value: Size) -> Option<Size> {
+        imp::successor(self, value)
+    }
+
+    /// Returns the largest integer in the set that is strictly less than `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = vec![1, 5, 100000].into_iter().collect();
+    /// assert_eq!(rb.predecessor(1), None);
+    /// assert_eq!(rb.predecessor(5), Some(1));
+    /// assert_eq!(rb.predecessor(100000), Some(5));
+    /// assert_eq!(rb.predecessor(u32::max_value()), Some(100000));
+    /// ```
+    #[inline]
+    pub fn predecessor(&self, value: Size) -> Option<Size> {
+        imp::predecessor(self, value)
+    }
+
+    /// Returns the number of integers in the set that are less than or equal to `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = vec![1, 5, 100000].into_iter().collect();
+    /// assert_eq!(rb.rank(0), 0);
+    /// assert_eq!(rb.rank(5), 2);
+    /// assert_eq!(rb.rank(99999), 2);
+    /// assert_eq!(rb.rank(100000), 3);
+    /// ```
+    #[inline]
+    pub fn rank(&self, value: Size) -> u64 {
+        imp::rank(self, value)
+    }
+
+    /// Returns the `n`th smallest integer in the set, counting from zero, or `None` if the set
+    /// has `n` or fewer integers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = vec![1, 5, 100000].into_iter().collect();
+    /// assert_eq!(rb.select(0), Some(1));
+    /// assert_eq!(rb.select(2), Some(100000));
+    /// assert_eq!(rb.select(3), None);
+    /// ```
+    #[inline]
+    pub fn select(&self, n: u64) -> Option<Size> {
+        imp::select(self, n)
+    }
+
     /// Iterator over each value stored in the RoaringBitmap, guarantees values are ordered by value.
     ///
     /// # Examples
@@ -202,6 +631,77 @@ impl<Size: ExtInt + Halveable> RoaringBitmap<Size> {
         imp::iter(self)
     }
 
+    /// Iterator over the values stored in the RoaringBitmap that lie in `range`, ordered by value.
+    /// Unlike `BTreeSet::range`, a range whose start is past its end yields nothing rather than
+    /// panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = vec![1, 4, 6, 100000].into_iter().collect();
+    ///
+    /// assert_eq!(rb.range(2..100000).collect::<Vec<u32>>(), vec![4, 6]);
+    /// assert_eq!(rb.range(4..).rev().collect::<Vec<u32>>(), vec![100000, 6, 4]);
+    /// ```
+    #[inline]
+    pub fn range<'a, R: RangeBounds<Size>>(&'a self, range: R) -> RangeIter<'a, Size> where <Size as Halveable>::HalfSize : 'a {
+        imp::range(self, range)
+    }
+
+    /// Returns a vector of the values stored in the RoaringBitmap, ordered by value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = vec![6, 1, 100000, 4].into_iter().collect();
+    ///
+    /// assert_eq!(rb.to_vec(), vec![1, 4, 6, 100000]);
+    /// ```
+    pub fn to_vec(&self) -> Vec<Size> {
+        let mut vec = vec![Zero::zero(); self.len() as usize];
+        self.iter().next_many(&mut vec);
+        vec
+    }
+
+    /// Copies the smallest values stored in the RoaringBitmap into `out`, in order, returning how
+    /// many were copied. This is the smaller of `out.len()` and `self.len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = vec![6, 1, 100000, 4].into_iter().collect();
+    /// let mut buffer = [0; 3];
+    ///
+    /// assert_eq!(rb.copy_to_slice(&mut buffer), 3);
+    /// assert_eq!(buffer, [1, 4, 6]);
+    /// ```
+    #[inline]
+    pub fn copy_to_slice(&self, out: &mut [Size]) -> usize {
+        self.iter().next_many(out)
+    }
+
+    /// Iterator over each value stored in the RoaringBitmap, from largest to smallest.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = vec![1, 4, 6, 100000].into_iter().collect();
+    ///
+    /// assert_eq!(rb.rev_iter().take(2).collect::<Vec<u32>>(), vec![100000, 6]);
+    /// ```
+    #[inline]
+    pub fn rev_iter<'a>(&'a self) -> Rev<Iter<'a, Size>> where <Size as Halveable>::HalfSize : 'a {
+        imp::iter(self).rev()
+    }
+
     /// Returns true if the set has no elements in common with other. This is equivalent to
     /// checking for an empty intersection.
     ///
@@ -227,6 +727,26 @@ impl<Size: ExtInt + Halveable> RoaringBitmap<Size> {
         imp::is_disjoint(self, other)
     }
 
+    /// Returns `true` if the set has any elements in common with `other`. This is the opposite
+    /// of `is_disjoint`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb1: RoaringBitmap<u32> = (1..4).collect();
+    /// let rb2: RoaringBitmap<u32> = (3..5).collect();
+    /// let rb3: RoaringBitmap<u32> = (4..5).collect();
+    ///
+    /// assert_eq!(rb1.intersects(&rb2), true);
+    /// assert_eq!(rb1.intersects(&rb3), false);
+    /// ```
+    #[inline]
+    pub fn intersects(&self, other: &Self) -> bool {
+        !imp::is_disjoint(self, other)
+    }
+
     /// Returns `true` if this set is a subset of `other`.
     ///
     /// # Examples
@@ -245,46 +765,138 @@ impl<Size: ExtInt + Halveable> RoaringBitmap<Size> {
     ///
     /// assert_eq!(rb1.is_subset(&rb2), true);
     ///
-    /// rb1.insert(2);
+    /// rb1.insert(2);
+    ///
+    /// assert_eq!(rb1.is_subset(&rb2), false);
+    /// ```
+    #[inline]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        imp::is_subset(self, other)
+    }
+
+    /// Returns `true` if this set is a subset of `other`.
+    #[inline]
+    pub fn is_subset_opt(&self, other: &Self) -> bool {
+        imp::is_subset_opt(self, other)
+    }
+
+    /// Returns `true` if this set is a superset of `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb1: RoaringBitmap<u32> = RoaringBitmap::new();
+    /// let mut rb2: RoaringBitmap<u32> = RoaringBitmap::new();
+    ///
+    /// rb1.insert(1);
+    ///
+    /// assert_eq!(rb2.is_superset(&rb1), false);
+    ///
+    /// rb2.insert(1);
+    ///
+    /// assert_eq!(rb2.is_superset(&rb1), true);
+    ///
+    /// rb1.insert(2);
+    ///
+    /// assert_eq!(rb2.is_superset(&rb1), false);
+    /// ```
+    #[inline]
+    pub fn is_superset(&self, other: &Self) -> bool {
+        imp::is_superset(self, other)
+    }
+
+    /// Returns the number of values in the intersection of this bitmap with `other`, without
+    /// building the intersection.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb1: RoaringBitmap<u32> = (1..4).collect();
+    /// let rb2: RoaringBitmap<u32> = (3..5).collect();
+    ///
+    /// assert_eq!(rb1.intersection_len(&rb2), 1);
+    /// ```
+    #[inline]
+    pub fn intersection_len(&self, other: &Self) -> u64 {
+        imp::intersection_len(self, other)
+    }
+
+    /// Returns the number of values in the union of this bitmap with `other`, without building
+    /// the union.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb1: RoaringBitmap<u32> = (1..4).collect();
+    /// let rb2: RoaringBitmap<u32> = (3..5).collect();
+    ///
+    /// assert_eq!(rb1.union_len(&rb2), 4);
+    /// ```
+    #[inline]
+    pub fn union_len(&self, other: &Self) -> u64 {
+        imp::union_len(self, other)
+    }
+
+    /// Returns the number of values in the difference of this bitmap and `other`, without
+    /// building the difference.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb1: RoaringBitmap<u32> = (1..4).collect();
+    /// let rb2: RoaringBitmap<u32> = (3..5).collect();
     ///
-    /// assert_eq!(rb1.is_subset(&rb2), false);
+    /// assert_eq!(rb1.difference_len(&rb2), 2);
     /// ```
     #[inline]
-    pub fn is_subset(&self, other: &Self) -> bool {
-        imp::is_subset(self, other)
-    }
-
-    /// Returns `true` if this set is a subset of `other`.
-    #[inline]
-    pub fn is_subset_opt(&self, other: &Self) -> bool {
-        imp::is_subset_opt(self, other)
+    pub fn difference_len(&self, other: &Self) -> u64 {
+        imp::difference_len(self, other)
     }
 
-    /// Returns `true` if this set is a superset of `other`.
+    /// Returns the number of values in the symmetric difference of this bitmap and `other`,
+    /// without building the symmetric difference.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use roaring::RoaringBitmap;
     ///
-    /// let mut rb1: RoaringBitmap<u32> = RoaringBitmap::new();
-    /// let mut rb2: RoaringBitmap<u32> = RoaringBitmap::new();
-    ///
-    /// rb1.insert(1);
+    /// let rb1: RoaringBitmap<u32> = (1..4).collect();
+    /// let rb2: RoaringBitmap<u32> = (3..5).collect();
     ///
-    /// assert_eq!(rb2.is_superset(&rb1), false);
+    /// assert_eq!(rb1.symmetric_difference_len(&rb2), 3);
+    /// ```
+    #[inline]
+    pub fn symmetric_difference_len(&self, other: &Self) -> u64 {
+        imp::symmetric_difference_len(self, other)
+    }
+
+    /// Returns the Jaccard index of this bitmap and `other`: the size of their intersection
+    /// divided by the size of their union. This is `NaN` if both bitmaps are empty.
     ///
-    /// rb2.insert(1);
+    /// # Examples
     ///
-    /// assert_eq!(rb2.is_superset(&rb1), true);
+    /// ```rust
+    /// use roaring::RoaringBitmap;
     ///
-    /// rb1.insert(2);
+    /// let rb1: RoaringBitmap<u32> = (1..4).collect();
+    /// let rb2: RoaringBitmap<u32> = (3..5).collect();
     ///
-    /// assert_eq!(rb2.is_superset(&rb1), false);
+    /// assert_eq!(rb1.jaccard_index(&rb2), 0.25);
     /// ```
     #[inline]
-    pub fn is_superset(&self, other: &Self) -> bool {
-        imp::is_superset(self, other)
+    pub fn jaccard_index(&self, other: &Self) -> f64 {
+        let intersection = imp::intersection_len(self, other);
+        let union = self.len() + other.len() - intersection;
+        intersection as f64 / union as f64
     }
 
     /// Returns an iterator over the union of this bitmap with the `other` bitmap.
@@ -487,18 +1099,164 @@ impl<Size: ExtInt + Halveable> RoaringBitmap<Size> {
         imp::symmetric_difference_with(self, other)
     }
 
+    /// Returns the union of all the given bitmaps. This is faster than repeatedly calling
+    /// `union_with`, as each container is only converted once at the end.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb1: RoaringBitmap<u32> = (1..4).collect();
+    /// let rb2: RoaringBitmap<u32> = (3..5).collect();
+    /// let rb3: RoaringBitmap<u32> = (100000..100002).collect();
+    ///
+    /// let rb = RoaringBitmap::union_all(vec![&rb1, &rb2, &rb3]);
+    ///
+    /// assert_eq!(rb, vec![1, 2, 3, 4, 100000, 100001].into_iter().collect());
+    /// ```
+    pub fn union_all<'a, I: IntoIterator<Item = &'a RoaringBitmap<Size>>>(bitmaps: I) -> RoaringBitmap<Size> where Size: 'a {
+        imp::union_all(bitmaps)
+    }
+
+    /// Returns the intersection of all the given bitmaps, or an empty bitmap if there are none.
+    /// Each key is skipped as soon as one bitmap lacks it or its intersection becomes empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb1: RoaringBitmap<u32> = (1..10).collect();
+    /// let rb2: RoaringBitmap<u32> = (3..20).collect();
+    /// let rb3: RoaringBitmap<u32> = (5..7).collect();
+    ///
+    /// let rb = RoaringBitmap::intersection_all(vec![&rb1, &rb2, &rb3]);
+    ///
+    /// assert_eq!(rb, (5..7).collect());
+    /// ```
+    pub fn intersection_all<'a, I: IntoIterator<Item = &'a RoaringBitmap<Size>>>(bitmaps: I) -> RoaringBitmap<Size> where Size: 'a {
+        imp::intersection_all(bitmaps)
+    }
+
+    /// Converts every container to whichever of the array, bitmap or run representations takes
+    /// the least space. Returns `true` if any container changed representation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb: RoaringBitmap<u32> = (0..4000u32).collect();
+    ///
+    /// assert_eq!(rb.run_optimize(), true);
+    /// assert_eq!(rb.run_optimize(), false);
+    /// assert_eq!(rb, (0..4000u32).collect());
+    /// ```
+    #[inline]
+    pub fn run_optimize(&mut self) -> bool {
+        imp::run_optimize(self)
+    }
+
+    /// Converts every run container back to an array or bitmap container. Returns `true` if any
+    /// container changed representation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb: RoaringBitmap<u32> = (0..60000u32).collect();
+    /// rb.run_optimize();
+    ///
+    /// assert_eq!(rb.remove_run_compression(), true);
+    /// assert_eq!(rb.remove_run_compression(), false);
+    /// assert_eq!(rb, (0..60000u32).collect());
+    /// ```
+    #[inline]
+    pub fn remove_run_compression(&mut self) -> bool {
+        imp::remove_run_compression(self)
+    }
+
+    /// Serializes this bitmap as a vector of `u64`s, as read by `from_raw64` and `try_from_raw64`.
     #[inline]
     pub fn to_raw64(&self) -> Vec<u64> {
         imp::to_raw64(self)
     }
 }
 
+impl RoaringBitmap<u32> {
+    /// Returns the number of bytes `serialize_into` will write for this bitmap.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb: RoaringBitmap<u32> = (1..4u32).collect();
+    /// let mut bytes = vec![];
+    /// rb.serialize_into(&mut bytes).unwrap();
+    ///
+    /// assert_eq!(rb.serialized_size(), bytes.len());
+    /// ```
+    #[inline]
+    pub fn serialized_size(&self) -> usize {
+        serialization::serialized_size(self)
+    }
+
+    /// Serializes this bitmap into the portable [Roaring format][format], readable by the C, Java
+    /// and Go implementations.
+    ///
+    /// [format]: https://github.com/RoaringBitmap/RoaringFormatSpec
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let rb1: RoaringBitmap<u32> = (1..4u32).collect();
+    /// let mut bytes = vec![];
+    /// rb1.serialize_into(&mut bytes).unwrap();
+    /// let rb2 = RoaringBitmap::deserialize_from(&bytes[..]).unwrap();
+    ///
+    /// assert_eq!(rb1, rb2);
+    /// ```
+    #[inline]
+    pub fn serialize_into<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        serialization::serialize_into(self, writer)
+    }
+
+    /// Deserializes a bitmap from the portable [Roaring format][format], checking that the data
+    /// describes a valid bitmap.
+    ///
+    /// [format]: https://github.com/RoaringBitmap/RoaringFormatSpec
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::{ RoaringBitmap, DeserializeError };
+    ///
+    /// let rb1: RoaringBitmap<u32> = (1..4u32).collect();
+    /// let mut bytes = vec![];
+    /// rb1.serialize_into(&mut bytes).unwrap();
+    ///
+    /// match RoaringBitmap::deserialize_from(&bytes[..4]) {
+    ///     Err(DeserializeError::UnexpectedEnd) => (),
+    ///     _ => panic!(),
+    /// }
+    /// ```
+    #[inline]
+    pub fn deserialize_from<R: io::Read>(reader: R) -> std::result::Result<Self, DeserializeError> {
+        serialization::deserialize_from(reader)
+    }
+}
+
 impl<Size: ExtInt + Halveable> IntoIterator for RoaringBitmap<Size> {
     type Item = Size;
-    type IntoIter = <Vec<Size> as IntoIterator>::IntoIter;
+    type IntoIter = IntoIter<Size>;
     #[inline]
-    fn into_iter(self) -> <Self as IntoIterator>::IntoIter {
-        imp::iter(&self).collect::<Vec<Size>>().into_iter()
+    fn into_iter(self) -> IntoIter<Size> {
+        iter::into_iter::new(self.containers.into_iter())
     }
 }
 
@@ -549,9 +1307,8 @@ impl<Size: ExtInt + Halveable> BitOr<RoaringBitmap<Size>> for RoaringBitmap<Size
     /// assert_eq!(rb3, rb4);
     /// ```
     #[inline]
-    fn bitor(mut self, rhs: Self) -> Self {
-        self.union_with(&rhs);
-        self
+    fn bitor(self, rhs: Self) -> Self {
+        imp::union_owned(self, rhs)
     }
 }
 
@@ -600,9 +1357,7 @@ impl<'a, 'b, Size: ExtInt + Halveable> BitOr<&'a RoaringBitmap<Size>> for &'b Ro
     /// ```
     #[inline]
     fn bitor(self, rhs: &'a RoaringBitmap<Size>) -> RoaringBitmap<Size> {
-        let mut result = self.clone();
-        result.union_with(rhs);
-        result
+        imp::union_cloned(self, rhs)
     }
 }
 
@@ -631,6 +1386,50 @@ impl<'a, Size: ExtInt + Halveable> BitOr<&'a RoaringBitmap<Size>> for RoaringBit
     }
 }
 
+impl<Size: ExtInt + Halveable> BitOrAssign<RoaringBitmap<Size>> for RoaringBitmap<Size> {
+    /// Unions the `rhs` into this `RoaringBitmap`, reusing its containers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb1: RoaringBitmap<u32> = (1..4u32).collect();
+    /// let rb2: RoaringBitmap<u32> = (2..5u32).collect();
+    /// let rb3: RoaringBitmap<u32> = (1..5u32).collect();
+    ///
+    /// rb1 |= rb2;
+    ///
+    /// assert_eq!(rb1, rb3);
+    /// ```
+    #[inline]
+    fn bitor_assign(&mut self, rhs: RoaringBitmap<Size>) {
+        *self = imp::union_owned(mem::replace(self, RoaringBitmap::new()), rhs)
+    }
+}
+
+impl<'a, Size: ExtInt + Halveable> BitOrAssign<&'a RoaringBitmap<Size>> for RoaringBitmap<Size> {
+    /// Unions the `rhs` into this `RoaringBitmap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb1: RoaringBitmap<u32> = (1..4u32).collect();
+    /// let rb2: RoaringBitmap<u32> = (2..5u32).collect();
+    /// let rb3: RoaringBitmap<u32> = (1..5u32).collect();
+    ///
+    /// rb1 |= &rb2;
+    ///
+    /// assert_eq!(rb1, rb3);
+    /// ```
+    #[inline]
+    fn bitor_assign(&mut self, rhs: &'a RoaringBitmap<Size>) {
+        self.union_with(rhs)
+    }
+}
+
 impl<Size: ExtInt + Halveable> BitAnd<RoaringBitmap<Size>> for RoaringBitmap<Size> {
     type Output = Self;
 
@@ -650,9 +1449,8 @@ impl<Size: ExtInt + Halveable> BitAnd<RoaringBitmap<Size>> for RoaringBitmap<Siz
     /// assert_eq!(rb3, rb4);
     /// ```
     #[inline]
-    fn bitand(mut self, rhs: Self) -> Self {
-        self.intersect_with(&rhs);
-        self
+    fn bitand(self, rhs: Self) -> Self {
+        imp::intersection_owned(self, rhs)
     }
 }
 
@@ -726,9 +1524,51 @@ impl<'a, 'b, Size: ExtInt + Halveable> BitAnd<&'a RoaringBitmap<Size>> for &'b R
     /// ```
     #[inline]
     fn bitand(self, rhs: &'a RoaringBitmap<Size>) -> RoaringBitmap<Size> {
-        let mut result = self.clone();
-        result.intersect_with(rhs);
-        result
+        imp::intersection_cloned(self, rhs)
+    }
+}
+
+impl<Size: ExtInt + Halveable> BitAndAssign<RoaringBitmap<Size>> for RoaringBitmap<Size> {
+    /// Intersects the `rhs` into this `RoaringBitmap`, reusing its containers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb1: RoaringBitmap<u32> = (1..4u32).collect();
+    /// let rb2: RoaringBitmap<u32> = (2..5u32).collect();
+    /// let rb3: RoaringBitmap<u32> = (2..4u32).collect();
+    ///
+    /// rb1 &= rb2;
+    ///
+    /// assert_eq!(rb1, rb3);
+    /// ```
+    #[inline]
+    fn bitand_assign(&mut self, rhs: RoaringBitmap<Size>) {
+        *self = imp::intersection_owned(mem::replace(self, RoaringBitmap::new()), rhs)
+    }
+}
+
+impl<'a, Size: ExtInt + Halveable> BitAndAssign<&'a RoaringBitmap<Size>> for RoaringBitmap<Size> {
+    /// Intersects the `rhs` into this `RoaringBitmap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb1: RoaringBitmap<u32> = (1..4u32).collect();
+    /// let rb2: RoaringBitmap<u32> = (2..5u32).collect();
+    /// let rb3: RoaringBitmap<u32> = (2..4u32).collect();
+    ///
+    /// rb1 &= &rb2;
+    ///
+    /// assert_eq!(rb1, rb3);
+    /// ```
+    #[inline]
+    fn bitand_assign(&mut self, rhs: &'a RoaringBitmap<Size>) {
+        self.intersect_with(rhs)
     }
 }
 
@@ -751,9 +1591,8 @@ impl<Size: ExtInt + Halveable> Sub<RoaringBitmap<Size>> for RoaringBitmap<Size>
     /// assert_eq!(rb3, rb4);
     /// ```
     #[inline]
-    fn sub(mut self, rhs: Self) -> Self {
-        self.difference_with(&rhs);
-        self
+    fn sub(self, rhs: Self) -> Self {
+        imp::difference_owned(self, rhs)
     }
 }
 
@@ -802,9 +1641,51 @@ impl<'a, 'b, Size: ExtInt + Halveable> Sub<&'a RoaringBitmap<Size>> for &'b Roar
     /// ```
     #[inline]
     fn sub(self, rhs: &'a RoaringBitmap<Size>) -> RoaringBitmap<Size> {
-        let mut result = self.clone();
-        result.difference_with(rhs);
-        result
+        imp::difference_cloned(self, rhs)
+    }
+}
+
+impl<Size: ExtInt + Halveable> SubAssign<RoaringBitmap<Size>> for RoaringBitmap<Size> {
+    /// Subtracts the `rhs` into this `RoaringBitmap`, reusing its containers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb1: RoaringBitmap<u32> = (1..4u32).collect();
+    /// let rb2: RoaringBitmap<u32> = (3..5u32).collect();
+    /// let rb3: RoaringBitmap<u32> = (1..3u32).collect();
+    ///
+    /// rb1 -= rb2;
+    ///
+    /// assert_eq!(rb1, rb3);
+    /// ```
+    #[inline]
+    fn sub_assign(&mut self, rhs: RoaringBitmap<Size>) {
+        *self = imp::difference_owned(mem::replace(self, RoaringBitmap::new()), rhs)
+    }
+}
+
+impl<'a, Size: ExtInt + Halveable> SubAssign<&'a RoaringBitmap<Size>> for RoaringBitmap<Size> {
+    /// Subtracts the `rhs` into this `RoaringBitmap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb1: RoaringBitmap<u32> = (1..4u32).collect();
+    /// let rb2: RoaringBitmap<u32> = (3..5u32).collect();
+    /// let rb3: RoaringBitmap<u32> = (1..3u32).collect();
+    ///
+    /// rb1 -= &rb2;
+    ///
+    /// assert_eq!(rb1, rb3);
+    /// ```
+    #[inline]
+    fn sub_assign(&mut self, rhs: &'a RoaringBitmap<Size>) {
+        self.difference_with(rhs)
     }
 }
 
@@ -827,9 +1708,8 @@ impl<Size: ExtInt + Halveable> BitXor<RoaringBitmap<Size>> for RoaringBitmap<Siz
     /// assert_eq!(rb3, rb4);
     /// ```
     #[inline]
-    fn bitxor(mut self, rhs: Self) -> Self {
-        self.symmetric_difference_with(&rhs);
-        self
+    fn bitxor(self, rhs: Self) -> Self {
+        imp::symmetric_difference_owned(self, rhs)
     }
 }
 
@@ -903,19 +1783,61 @@ impl<'a, 'b, Size: ExtInt + Halveable> BitXor<&'a RoaringBitmap<Size>> for &'b R
     /// ```
     #[inline]
     fn bitxor(self, rhs: &'a RoaringBitmap<Size>) -> RoaringBitmap<Size> {
-        let mut result = self.clone();
-        result.symmetric_difference_with(rhs);
-        result
+        imp::symmetric_difference_cloned(self, rhs)
+    }
+}
+
+impl<Size: ExtInt + Halveable> BitXorAssign<RoaringBitmap<Size>> for RoaringBitmap<Size> {
+    /// Exclusive ors the `rhs` into this `RoaringBitmap`, reusing its containers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb1: RoaringBitmap<u32> = (1..4u32).collect();
+    /// let rb2: RoaringBitmap<u32> = (3..6u32).collect();
+    /// let rb3: RoaringBitmap<u32> = ((1..3u32).chain(4..6u32)).collect();
+    ///
+    /// rb1 ^= rb2;
+    ///
+    /// assert_eq!(rb1, rb3);
+    /// ```
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: RoaringBitmap<Size>) {
+        *self = imp::symmetric_difference_owned(mem::replace(self, RoaringBitmap::new()), rhs)
+    }
+}
+
+impl<'a, Size: ExtInt + Halveable> BitXorAssign<&'a RoaringBitmap<Size>> for RoaringBitmap<Size> {
+    /// Exclusive ors the `rhs` into this `RoaringBitmap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringBitmap;
+    ///
+    /// let mut rb1: RoaringBitmap<u32> = (1..4u32).collect();
+    /// let rb2: RoaringBitmap<u32> = (3..6u32).collect();
+    /// let rb3: RoaringBitmap<u32> = ((1..3u32).chain(4..6u32)).collect();
+    ///
+    /// rb1 ^= &rb2;
+    ///
+    /// assert_eq!(rb1, rb3);
+    /// ```
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: &'a RoaringBitmap<Size>) {
+        self.symmetric_difference_with(rhs)
     }
 }
 
 impl<Size: ExtInt + Halveable + Debug> Debug for RoaringBitmap<Size> {
     #[inline]
     fn fmt(&self, formatter: &mut Formatter) -> Result {
-        if self.len() < util::cast(16u8) {
+        if self.len() < 16 {
             format!("RoaringBitmap<{:?}>", self.iter().collect::<Vec<Size>>()).fmt(formatter)
         } else {
-            format!("RoaringBitmap<{:?} values between {:?} and {:?}>", self.len(), imp::min(self), imp::max(self)).fmt(formatter)
+            format!("RoaringBitmap<{:?} values between {:?} and {:?}>", self.len(), imp::min(self).unwrap(), imp::max(self).unwrap()).fmt(formatter)
         }
     }
 }
@@ -0,0 +1,198 @@
+// The portable format shared with the C, Java and Go implementations, as described at
+// https://github.com/RoaringBitmap/RoaringFormatSpec
+
+use std::io::{ self, Read, Write };
+
+use container::Container;
+use error::DeserializeError;
+use store::Store::{ self, Array, Bitmap, Run };
+
+use RoaringBitmap as RB;
+
+const SERIAL_COOKIE_NO_RUNCONTAINER: u32 = 12346;
+const SERIAL_COOKIE: u16 = 12347;
+const NO_OFFSET_THRESHOLD: usize = 4;
+const ARRAY_LIMIT: u64 = 4096;
+const BITMAP_LENGTH: usize = 1024;
+
+fn has_runs(this: &RB<u32>) -> bool {
+    this.containers.iter().any(|container| match *container.store() { Run(..) => true, _ => false })
+}
+
+fn store_size(store: &Store<u16>) -> usize {
+    match *store {
+        Array(ref vec) => 2 * vec.len(),
+        Bitmap(..) => 8 * BITMAP_LENGTH,
+        Run(ref runs) => 2 + 4 * runs.len(),
+    }
+}
+
+fn header_size(this: &RB<u32>) -> usize {
+    let size = this.containers.len();
+    if has_runs(this) {
+        let offsets = if size >= NO_OFFSET_THRESHOLD { 4 * size } else { 0 };
+        4 + (size + 7) / 8 + 4 * size + offsets
+    } else {
+        8 + 8 * size
+    }
+}
+
+pub fn serialized_size(this: &RB<u32>) -> usize {
+    header_size(this) + this.containers.iter().map(|container| store_size(container.store())).sum::<usize>()
+}
+
+pub fn serialize_into<W: Write>(this: &RB<u32>, mut writer: W) -> io::Result<()> {
+    let size = this.containers.len();
+    let has_runs = has_runs(this);
+
+    if has_runs {
+        write_u32(&mut writer, SERIAL_COOKIE as u32 | ((size as u32 - 1) << 16))?;
+        let mut run_flags = vec![0u8; (size + 7) / 8];
+        for (i, container) in this.containers.iter().enumerate() {
+            if let Run(..) = *container.store() {
+                run_flags[i / 8] |= 1 << (i % 8);
+            }
+        }
+        writer.write_all(&run_flags)?;
+    } else {
+        write_u32(&mut writer, SERIAL_COOKIE_NO_RUNCONTAINER)?;
+        write_u32(&mut writer, size as u32)?;
+    }
+
+    for container in &this.containers {
+        write_u16(&mut writer, container.key())?;
+        write_u16(&mut writer, (container.len() - 1) as u16)?;
+    }
+
+    if !has_runs || size >= NO_OFFSET_THRESHOLD {
+        let mut offset = header_size(this);
+        for container in &this.containers {
+            write_u32(&mut writer, offset as u32)?;
+            offset += store_size(container.store());
+        }
+    }
+
+    for container in &this.containers {
+        match *container.store() {
+            Array(ref vec) => {
+                for &value in vec {
+                    write_u16(&mut writer, value)?;
+                }
+            },
+            Bitmap(ref bits) => {
+                for &word in bits.iter() {
+                    write_u64(&mut writer, word)?;
+                }
+            },
+            Run(ref runs) => {
+                write_u16(&mut writer, runs.len() as u16)?;
+                for &(start, length) in runs {
+                    write_u16(&mut writer, start)?;
+                    write_u16(&mut writer, length)?;
+                }
+            },
+        }
+    }
+
+    Ok(())
+}
+
+pub fn deserialize_from<R: Read>(mut reader: R) -> Result<RB<u32>, DeserializeError> {
+    let cookie = read_u32(&mut reader)?;
+    let (size, run_flags) = if cookie == SERIAL_COOKIE_NO_RUNCONTAINER {
+        (read_u32(&mut reader)? as usize, None)
+    } else if cookie as u16 == SERIAL_COOKIE {
+        let size = (cookie >> 16) as usize + 1;
+        let mut run_flags = vec![0u8; (size + 7) / 8];
+        reader.read_exact(&mut run_flags)?;
+        (size, Some(run_flags))
+    } else {
+        return Err(DeserializeError::InvalidCookie(cookie));
+    };
+    if size > 1 << 16 {
+        return Err(DeserializeError::ValueOutOfRange(size as u64));
+    }
+
+    let mut description = Vec::with_capacity(size);
+    for _ in 0..size {
+        let key = read_u16(&mut reader)?;
+        let len = read_u16(&mut reader)? as u64 + 1;
+        description.push((key, len));
+    }
+
+    if run_flags.is_none() || size >= NO_OFFSET_THRESHOLD {
+        // The containers are stored contiguously, so the offsets aren't needed.
+        for _ in 0..size {
+            read_u32(&mut reader)?;
+        }
+    }
+
+    let mut rb = RB::new();
+    for (i, (key, len)) in description.into_iter().enumerate() {
+        let is_run = run_flags.as_ref().map_or(false, |flags| flags[i / 8] & (1 << (i % 8)) != 0);
+        let store = if is_run {
+            let count = read_u16(&mut reader)?;
+            let mut runs = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                let start = read_u16(&mut reader)?;
+                let length = read_u16(&mut reader)?;
+                runs.push((start, length));
+            }
+            Run(runs)
+        } else if len <= ARRAY_LIMIT {
+            let mut vec = Vec::with_capacity(len as usize);
+            for _ in 0..len {
+                vec.push(read_u16(&mut reader)?);
+            }
+            Array(vec)
+        } else {
+            let mut bits = vec![0u64; BITMAP_LENGTH];
+            for word in bits.iter_mut() {
+                *word = read_u64(&mut reader)?;
+            }
+            Bitmap(bits.into_boxed_slice())
+        };
+        if rb.containers.last().map_or(false, |last: &Container<u16>| last.key() >= key) {
+            return Err(DeserializeError::UnsortedKeys);
+        }
+        rb.containers.push(Container::try_from_store(key, len, store)?);
+    }
+
+    Ok(rb)
+}
+
+#[inline]
+fn write_u16<W: Write>(writer: &mut W, value: u16) -> io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+#[inline]
+pub fn write_u32<W: Write>(writer: &mut W, value: u32) -> io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+#[inline]
+fn read_u16<R: Read>(reader: &mut R) -> io::Result<u16> {
+    let mut bytes = [0; 2];
+    reader.read_exact(&mut bytes)?;
+    Ok(u16::from_le_bytes(bytes))
+}
+
+#[inline]
+pub fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+#[inline]
+pub fn write_u64<W: Write>(writer: &mut W, value: u64) -> io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+#[inline]
+pub fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
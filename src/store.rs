@@ -1,15 +1,21 @@
-use std::iter;
+use std::cmp;
+use std::vec;
+use std::slice;
+use std::borrow::Borrow;
 use std::marker::PhantomData;
 use std::cmp::Ordering::{ Equal, Less, Greater };
 
-use num::traits::{ Zero, Bounded };
+use num::traits::{ Zero, One, Bounded };
 
 use util::{ self, ExtInt };
-use store::Store::{ Array, Bitmap };
+use error::DeserializeError;
+use store::Store::{ Array, Bitmap, Run };
 
 pub enum Store<Size: ExtInt> {
     Array(Vec<Size>),
     Bitmap(Box<[u64]>),
+    // Sorted `(start, length)` intervals, each covering `start...start + length`.
+    Run(Vec<(Size, Size)>),
 }
 
 impl<Size: ExtInt> Store<Size> {
@@ -29,6 +35,46 @@ impl<Size: ExtInt> Store<Size> {
                     false
                 }
             },
+            Run(ref mut runs) => {
+                let loc = match run_search(runs, index) {
+                    Ok(_) => return false,
+                    Err(loc) => loc,
+                };
+                let value = index.to64();
+                let joins_prev = loc > 0 && run_end(&runs[loc - 1]) + 1 == value;
+                let joins_next = loc < runs.len() && runs[loc].0.to64() == value + 1;
+                match (joins_prev, joins_next) {
+                    (true, true) => {
+                        let (_, length) = runs.remove(loc);
+                        runs[loc - 1].1 = util::cast(runs[loc - 1].1.to64() + length.to64() + 2);
+                    },
+                    (true, false) => runs[loc - 1].1 = runs[loc - 1].1 + One::one(),
+                    (false, true) => runs[loc] = (index, runs[loc].1 + One::one()),
+                    (false, false) => runs.insert(loc, (index, Zero::zero())),
+                }
+                true
+            },
+        }
+    }
+
+    // Appends `index`, which must be greater than every value already in the store.
+    pub fn push(&mut self, index: Size) {
+        match *self {
+            Array(ref mut vec) => vec.push(index),
+            Bitmap(ref mut bits) => {
+                let (key, bit) = bitmap_location(index);
+                bits[key] |= 1 << bit;
+            },
+            Run(ref mut runs) => {
+                match runs.last_mut() {
+                    Some(run) if run_end(run) + 1 == index.to64() => {
+                        run.1 = run.1 + One::one();
+                        return;
+                    },
+                    _ => (),
+                }
+                runs.push((index, Zero::zero()));
+            },
         }
     }
 
@@ -48,6 +94,24 @@ impl<Size: ExtInt> Store<Size> {
                     false
                 }
             },
+            Run(ref mut runs) => {
+                let loc = match run_search(runs, index) {
+                    Ok(loc) => loc,
+                    Err(_) => return false,
+                };
+                let (start, end, value) = (runs[loc].0.to64(), run_end(&runs[loc]), index.to64());
+                if start == end {
+                    runs.remove(loc);
+                } else if value == start {
+                    runs[loc] = (util::cast(start + 1), util::cast(end - start - 1));
+                } else if value == end {
+                    runs[loc].1 = util::cast(end - start - 1);
+                } else {
+                    runs[loc].1 = util::cast(value - start - 1);
+                    runs.insert(loc + 1, (util::cast(value + 1), util::cast(end - value - 1)));
+                }
+                true
+            },
         }
     }
 
@@ -55,7 +119,8 @@ impl<Size: ExtInt> Store<Size> {
     pub fn contains(&self, index: Size) -> bool {
         match *self {
             Array(ref vec) => vec.binary_search(&index).is_ok(),
-            Bitmap(ref bits) => bits[key(index)] & (1 << bit(index)) != 0
+            Bitmap(ref bits) => bits[key(index)] & (1 << bit(index)) != 0,
+            Run(ref runs) => run_search(runs, index).is_ok(),
         }
     }
 
@@ -76,9 +141,51 @@ impl<Size: ExtInt> Store<Size> {
             (&Bitmap(ref bits1), &Bitmap(ref bits2)) => {
                 bits1.iter().zip(bits2.iter()).all(|(&i1, &i2)| (i1 & i2) == 0)
             },
-            (&Array(ref vec), store @ &Bitmap(..)) | (store @ &Bitmap(..), &Array(ref vec)) => {
+            (&Array(ref vec), store @ &Bitmap(..)) | (store @ &Bitmap(..), &Array(ref vec)) |
+            (&Array(ref vec), store @ &Run(..)) | (store @ &Run(..), &Array(ref vec)) => {
                 vec.iter().all(|&i| !store.contains(i))
             },
+            (&Run(ref runs1), &Run(ref runs2)) => {
+                run_op(runs1, runs2, |in1, in2| in1 && in2).is_empty()
+            },
+            (&Run(ref runs), &Bitmap(ref bits)) | (&Bitmap(ref bits), &Run(ref runs)) => {
+                runs.iter().all(|run| count_range(bits, run.0.to64(), run_end(run)) == 0)
+            },
+        }
+    }
+
+    pub fn intersection_len(&self, other: &Self) -> u64 {
+        match (self, other) {
+            (&Array(ref vec1), &Array(ref vec2)) => {
+                let (mut i1, mut i2) = (vec1.iter(), vec2.iter());
+                let (mut value1, mut value2) = (i1.next(), i2.next());
+                let mut count = 0;
+                loop {
+                    match value1.and_then(|v1| value2.map(|v2| v1.cmp(v2))) {
+                        None => return count,
+                        Some(Equal) => {
+                            count += 1;
+                            value1 = i1.next();
+                            value2 = i2.next();
+                        },
+                        Some(Less) => value1 = i1.next(),
+                        Some(Greater) => value2 = i2.next(),
+                    }
+                }
+            },
+            (&Bitmap(ref bits1), &Bitmap(ref bits2)) => {
+                bits1.iter().zip(bits2.iter()).map(|(&i1, &i2)| (i1 & i2).count_ones() as u64).sum()
+            },
+            (&Array(ref vec), store @ &Bitmap(..)) | (store @ &Bitmap(..), &Array(ref vec)) |
+            (&Array(ref vec), store @ &Run(..)) | (store @ &Run(..), &Array(ref vec)) => {
+                vec.iter().filter(|&&i| store.contains(i)).count() as u64
+            },
+            (&Run(ref runs1), &Run(ref runs2)) => {
+                runs_len(&run_op(runs1, runs2, |in1, in2| in1 && in2))
+            },
+            (&Run(ref runs), &Bitmap(ref bits)) | (&Bitmap(ref bits), &Run(ref runs)) => {
+                runs.iter().map(|run| count_range(bits, run.0.to64(), run_end(run))).sum()
+            },
         }
     }
 
@@ -105,10 +212,19 @@ impl<Size: ExtInt> Store<Size> {
             (&Bitmap(ref bits1), &Bitmap(ref bits2)) => {
                 bits1.iter().zip(bits2.iter()).all(|(&i1, &i2)| (i1 & i2) == i1)
             },
-            (&Array(ref vec), store @ &Bitmap(..)) => {
+            (&Array(ref vec), store @ &Bitmap(..)) | (&Array(ref vec), store @ &Run(..)) => {
                 vec.iter().all(|&i| store.contains(i))
             },
             (&Bitmap(..), &Array(..)) => false,
+            (&Run(ref runs1), &Run(ref runs2)) => {
+                run_op(runs1, runs2, |in1, in2| in1 && !in2).is_empty()
+            },
+            (&Run(ref runs), &Bitmap(ref bits)) => {
+                runs.iter().all(|run| count_range(bits, run.0.to64(), run_end(run)) == run.1.to64() + 1)
+            },
+            (this @ &Run(..), store @ &Array(..)) | (this @ &Bitmap(..), store @ &Run(..)) => {
+                this.iter().all(|i| store.contains(i))
+            },
         }
     }
 
@@ -126,25 +242,262 @@ impl<Size: ExtInt> Store<Size> {
                 }
                 Array(vec)
             },
+            Run(ref runs) => {
+                Array(runs.iter().flat_map(|run| (run.0.to64()..(run_end(run) + 1)).map(util::cast)).collect())
+            },
         }
     }
 
     pub fn to_bitmap(&self) -> Self {
         match *self {
             Array(ref vec) => {
-                let count = util::cast::<Size, usize>(Bounded::max_value()) / 64 + 1;
-                let mut bits = iter::repeat(0).take(count).collect::<Vec<u64>>().into_boxed_slice();
+                let mut bits = empty_bitmap::<Size>();
                 for &index in vec.iter() {
                     bits[key(index)] |= 1 << bit(index);
                 }
                 Bitmap(bits)
             },
             Bitmap(..) => panic!("Cannot convert bitmap to bitmap"),
+            Run(ref runs) => {
+                let mut bits = empty_bitmap::<Size>();
+                for run in runs.iter() {
+                    set_range(&mut bits, run.0.to64(), run_end(run));
+                }
+                Bitmap(bits)
+            },
         }
     }
 
+    // Sets the bits for this store's values in `bits`, without tracking the cardinality.
+    pub fn union_into(&self, bits: &mut [u64]) {
+        match *self {
+            Array(ref vec) => {
+                for &index in vec.iter() {
+                    bits[key(index)] |= 1 << bit(index);
+                }
+            },
+            Bitmap(ref bits2) => {
+                for (index1, &index2) in bits.iter_mut().zip(bits2.iter()) {
+                    *index1 |= index2;
+                }
+            },
+            Run(ref runs) => {
+                for run in runs.iter() {
+                    set_range(bits, run.0.to64(), run_end(run));
+                }
+            },
+        }
+    }
+
+    pub fn to_run(&self) -> Self {
+        match *self {
+            Array(ref vec) => Run(array_to_runs(vec)),
+            Bitmap(ref bits) => {
+                let mut runs: Vec<(Size, Size)> = Vec::new();
+                let mut start = None;
+                for (key, &word) in bits.iter().enumerate() {
+                    if (word == 0 && start.is_none()) || (word == !0 && start.is_some()) {
+                        continue;
+                    }
+                    for bit in 0..64 {
+                        let index = (key * 64 + bit) as u64;
+                        match (word & (1 << bit) != 0, start) {
+                            (true, None) => start = Some(index),
+                            (false, Some(s)) => {
+                                runs.push((util::cast(s), util::cast(index - s - 1)));
+                                start = None;
+                            },
+                            _ => (),
+                        }
+                    }
+                }
+                if let Some(s) = start {
+                    runs.push((util::cast(s), util::cast(capacity::<Size>() - s - 1)));
+                }
+                Run(runs)
+            },
+            Run(..) => panic!("Cannot convert run to run"),
+        }
+    }
+
+    /// Number of runs needed to represent this store as a `Run`.
+    pub fn run_count(&self) -> u64 {
+        match *self {
+            Array(ref vec) => {
+                let mut count = 0;
+                let mut previous = None;
+                for &index in vec.iter() {
+                    if previous.map_or(true, |p: Size| p + One::one() != index) {
+                        count += 1;
+                    }
+                    previous = Some(index);
+                }
+                count
+            },
+            Bitmap(ref bits) => {
+                let mut count = 0;
+                let mut carry = 0;
+                for &word in bits.iter() {
+                    // Count the bits that start a run: set, with the bit below them unset.
+                    count += (word & !((word << 1) | carry)).count_ones() as u64;
+                    carry = word >> 63;
+                }
+                count
+            },
+            Run(ref runs) => runs.len() as u64,
+        }
+    }
+
+    // Inserts the inclusive range `start...end`, returning how many values were added.
+    pub fn insert_range(&mut self, start: Size, end: Size) -> u64 {
+        let count = end.to64() - start.to64() + 1;
+        match *self {
+            Array(ref mut vec) => {
+                let first = vec.binary_search(&start).unwrap_or_else(|loc| loc);
+                let last = vec.binary_search(&end).map(|loc| loc + 1).unwrap_or_else(|loc| loc);
+                vec.splice(first..last, (start.to64()..(end.to64() + 1)).map(util::cast));
+                count - (last - first) as u64
+            },
+            Bitmap(ref mut bits) => {
+                let existing = count_range(bits, start.to64(), end.to64());
+                set_range(bits, start.to64(), end.to64());
+                count - existing
+            },
+            Run(ref mut runs) => {
+                let before = runs_len(runs);
+                *runs = run_op(runs, &[(start, end - start)], |in1, in2| in1 || in2);
+                runs_len(runs) - before
+            },
+        }
+    }
+
+    // Removes the inclusive range `start...end`, returning how many values were removed.
+    pub fn remove_range(&mut self, start: Size, end: Size) -> u64 {
+        match *self {
+            Array(ref mut vec) => {
+                let first = vec.binary_search(&start).unwrap_or_else(|loc| loc);
+                let last = vec.binary_search(&end).map(|loc| loc + 1).unwrap_or_else(|loc| loc);
+                vec.drain(first..last);
+                (last - first) as u64
+            },
+            Bitmap(ref mut bits) => {
+                let existing = count_range(bits, start.to64(), end.to64());
+                clear_range(bits, start.to64(), end.to64());
+                existing
+            },
+            Run(ref mut runs) => {
+                let before = runs_len(runs);
+                *runs = run_op(runs, &[(start, end - start)], |in1, in2| in1 && !in2);
+                before - runs_len(runs)
+            },
+        }
+    }
+
+    // Flips the inclusive range `start...end`, returning how many values were in it beforehand.
+    pub fn flip_range(&mut self, start: Size, end: Size) -> u64 {
+        match *self {
+            Array(ref mut vec) => {
+                let first = vec.binary_search(&start).unwrap_or_else(|loc| loc);
+                let last = vec.binary_search(&end).map(|loc| loc + 1).unwrap_or_else(|loc| loc);
+                let mut complement = Vec::with_capacity((end.to64() - start.to64() + 1) as usize - (last - first));
+                let mut next = start.to64();
+                for &index in &vec[first..last] {
+                    complement.extend((next..index.to64()).map(util::cast::<u64, Size>));
+                    next = index.to64() + 1;
+                }
+                complement.extend((next..(end.to64() + 1)).map(util::cast::<u64, Size>));
+                vec.splice(first..last, complement);
+                (last - first) as u64
+            },
+            Bitmap(ref mut bits) => {
+                let existing = count_range(bits, start.to64(), end.to64());
+                flip_range(bits, start.to64(), end.to64());
+                existing
+            },
+            Run(ref mut runs) => {
+                let before = runs_len(runs);
+                *runs = run_op(runs, &[(start, end - start)], |in1, in2| in1 != in2);
+                // Every value in the range was either removed or added.
+                (before + end.to64() - start.to64() + 1 - runs_len(runs)) / 2
+            },
+        }
+    }
+
+    // Moves the values not less than `index` into a new store of the same kind.
+    pub fn split_off(&mut self, index: Size) -> Self {
+        match *self {
+            Array(ref mut vec) => {
+                let loc = vec.binary_search(&index).unwrap_or_else(|loc| loc);
+                Array(vec.split_off(loc))
+            },
+            Bitmap(ref mut bits) => {
+                let mut other = bits.clone();
+                if index != Zero::zero() {
+                    clear_range(&mut other, 0, index.to64() - 1);
+                }
+                clear_range(bits, index.to64(), capacity::<Size>() - 1);
+                Bitmap(other)
+            },
+            Run(ref mut runs) => {
+                let loc = match run_search(runs, index) {
+                    Ok(loc) if runs[loc].0 == index => loc,
+                    Ok(loc) => {
+                        // Cut the run containing `index` in two.
+                        let (start, length) = runs[loc];
+                        runs[loc].1 = index - start - One::one();
+                        runs.insert(loc + 1, (index, start + length - index));
+                        loc + 1
+                    },
+                    Err(loc) => loc,
+                };
+                Run(runs.split_off(loc))
+            },
+        }
+    }
+
+    // Checks the invariants that can't be enforced by the type of a deserialized store.
+    pub fn validate(&self) -> Result<(), DeserializeError> {
+        match *self {
+            Array(ref vec) => {
+                if vec.windows(2).any(|pair| pair[0] >= pair[1]) {
+                    return Err(DeserializeError::UnsortedValues);
+                }
+            },
+            Bitmap(..) => (),
+            Run(ref runs) => {
+                let mut min_start = 0;
+                for run in runs.iter() {
+                    if run.0.to64() < min_start {
+                        return Err(DeserializeError::InvalidRuns);
+                    }
+                    if run_end(run) >= capacity::<Size>() {
+                        return Err(DeserializeError::ValueOutOfRange(run_end(run)));
+                    }
+                    min_start = run_end(run) + 2;
+                }
+            },
+        }
+        Ok(())
+    }
+
     pub fn union_with(&mut self, other: &Self) {
         match (self, other) {
+            (&mut Run(ref mut runs1), &Run(ref runs2)) => {
+                *runs1 = run_op(runs1, runs2, |in1, in2| in1 || in2);
+            },
+            (&mut Run(ref mut runs), &Array(ref vec)) => {
+                *runs = run_op(runs, &array_to_runs(vec), |in1, in2| in1 || in2);
+            },
+            (&mut Bitmap(ref mut bits), &Run(ref runs)) => {
+                for run in runs.iter() {
+                    set_range(bits, run.0.to64(), run_end(run));
+                }
+            },
+            (this @ &mut Array(..), &Run(..)) | (this @ &mut Run(..), &Bitmap(..)) => {
+                let mut new = other.clone();
+                new.union_with(this);
+                *this = new;
+            },
             (ref mut this, &Array(ref vec)) => {
                 for &index in vec.iter() {
                     this.insert(index);
@@ -191,11 +544,30 @@ impl<Size: ExtInt> Store<Size> {
                     }
                 }
             },
-            (this @ &mut Bitmap(..), &Array(..)) => {
+            (this @ &mut Bitmap(..), &Array(..)) | (this @ &mut Run(..), &Array(..)) |
+            (this @ &mut Run(..), &Bitmap(..)) => {
                 let mut new = other.clone();
                 new.intersect_with(this);
                 *this = new;
             },
+            (&mut Run(ref mut runs1), &Run(ref runs2)) => {
+                *runs1 = run_op(runs1, runs2, |in1, in2| in1 && in2);
+            },
+            (&mut Array(ref mut vec), &Run(ref runs)) => {
+                vec.retain(|&i| run_search(runs, i).is_ok());
+            },
+            (&mut Bitmap(ref mut bits), &Run(ref runs)) => {
+                let mut start = 0;
+                for run in runs.iter() {
+                    if run.0.to64() > start {
+                        clear_range(bits, start, run.0.to64() - 1);
+                    }
+                    start = run_end(run) + 1;
+                }
+                if start < capacity::<Size>() {
+                    clear_range(bits, start, capacity::<Size>() - 1);
+                }
+            },
         }
     }
 
@@ -234,6 +606,24 @@ impl<Size: ExtInt> Store<Size> {
                     }
                 }
             },
+            (&mut Run(ref mut runs1), &Run(ref runs2)) => {
+                *runs1 = run_op(runs1, runs2, |in1, in2| in1 && !in2);
+            },
+            (&mut Run(ref mut runs), &Array(ref vec)) => {
+                *runs = run_op(runs, &array_to_runs(vec), |in1, in2| in1 && !in2);
+            },
+            (this @ &mut Run(..), &Bitmap(..)) => {
+                *this = this.to_bitmap();
+                this.difference_with(other);
+            },
+            (&mut Array(ref mut vec), &Run(ref runs)) => {
+                vec.retain(|&i| run_search(runs, i).is_err());
+            },
+            (&mut Bitmap(ref mut bits), &Run(ref runs)) => {
+                for run in runs.iter() {
+                    clear_range(bits, run.0.to64(), run_end(run));
+                }
+            },
         }
     }
 
@@ -277,11 +667,130 @@ impl<Size: ExtInt> Store<Size> {
                     *index1 ^= index2;
                 }
             },
-            (this @ &mut Array(..), &Bitmap(..)) => {
+            (this @ &mut Array(..), &Bitmap(..)) | (this @ &mut Array(..), &Run(..)) |
+            (this @ &mut Run(..), &Bitmap(..)) => {
                 let mut new = other.clone();
                 new.symmetric_difference_with(this);
                 *this = new;
             },
+            (&mut Run(ref mut runs1), &Run(ref runs2)) => {
+                *runs1 = run_op(runs1, runs2, |in1, in2| in1 != in2);
+            },
+            (&mut Run(ref mut runs), &Array(ref vec)) => {
+                *runs = run_op(runs, &array_to_runs(vec), |in1, in2| in1 != in2);
+            },
+            (&mut Bitmap(ref mut bits), &Run(ref runs)) => {
+                for run in runs.iter() {
+                    flip_range(bits, run.0.to64(), run_end(run));
+                }
+            },
+        }
+    }
+
+    // The binary operations compute a new store from two borrowed ones, merging arrays and runs
+    // directly and combining everything else a word at a time.
+    pub fn union(&self, other: &Self) -> Self {
+        match (self, other) {
+            (&Array(ref vec1), &Array(ref vec2)) => Array(array_op(vec1, vec2, true, true, true)),
+            (&Run(ref runs1), &Run(ref runs2)) => Run(run_op(runs1, runs2, |in1, in2| in1 || in2)),
+            (&Run(ref runs), &Array(ref vec)) | (&Array(ref vec), &Run(ref runs)) => {
+                Run(run_op(runs, &array_to_runs(vec), |in1, in2| in1 || in2))
+            },
+            _ => words_op(self, other, |word1, word2| word1 | word2),
+        }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        match (self, other) {
+            (&Array(ref vec1), &Array(ref vec2)) => Array(array_op(vec1, vec2, false, true, false)),
+            (&Array(ref vec), store) | (store, &Array(ref vec)) => {
+                Array(vec.iter().cloned().filter(|&index| store.contains(index)).collect())
+            },
+            (&Run(ref runs1), &Run(ref runs2)) => Run(run_op(runs1, runs2, |in1, in2| in1 && in2)),
+            _ => words_op(self, other, |word1, word2| word1 & word2),
+        }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        match (self, other) {
+            (&Array(ref vec1), &Array(ref vec2)) => Array(array_op(vec1, vec2, true, false, false)),
+            (&Array(ref vec), _) => Array(vec.iter().cloned().filter(|&index| !other.contains(index)).collect()),
+            (&Run(ref runs1), &Run(ref runs2)) => Run(run_op(runs1, runs2, |in1, in2| in1 && !in2)),
+            (&Run(ref runs), &Array(ref vec)) => Run(run_op(runs, &array_to_runs(vec), |in1, in2| in1 && !in2)),
+            _ => words_op(self, other, |word1, word2| word1 & !word2),
+        }
+    }
+
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        match (self, other) {
+            (&Array(ref vec1), &Array(ref vec2)) => Array(array_op(vec1, vec2, true, false, true)),
+            (&Run(ref runs1), &Run(ref runs2)) => Run(run_op(runs1, runs2, |in1, in2| in1 != in2)),
+            (&Run(ref runs), &Array(ref vec)) | (&Array(ref vec), &Run(ref runs)) => {
+                Run(run_op(runs, &array_to_runs(vec), |in1, in2| in1 != in2))
+            },
+            _ => words_op(self, other, |word1, word2| word1 ^ word2),
+        }
+    }
+
+    // Returns the store as a bitmap buffer.
+    fn words(&self) -> Box<[u64]> {
+        match *self {
+            Bitmap(ref bits) => bits.clone(),
+            _ => {
+                let mut bits = empty_bitmap::<Size>();
+                self.union_into(&mut bits);
+                bits
+            },
+        }
+    }
+
+    // The `*_owned` operations consume both stores and, for the commutative ones, run in place on
+    // whichever store the `*_with` version would otherwise have to clone, so a bitmap buffer on
+    // either side is reused.
+    pub fn union_owned(mut self, mut other: Self) -> Self {
+        if union_swaps(&self, &other) {
+            other.union_with(&self);
+            other
+        } else {
+            self.union_with(&other);
+            self
+        }
+    }
+
+    pub fn intersection_owned(mut self, mut other: Self) -> Self {
+        let swap = match (&self, &other) {
+            (&Bitmap(..), &Array(..)) | (&Run(..), &Array(..)) | (&Run(..), &Bitmap(..)) => true,
+            _ => false,
+        };
+        if swap {
+            other.intersect_with(&self);
+            other
+        } else {
+            self.intersect_with(&other);
+            self
+        }
+    }
+
+    pub fn difference_owned(mut self, other: Self) -> Self {
+        self.difference_with(&other);
+        self
+    }
+
+    pub fn symmetric_difference_owned(mut self, mut other: Self) -> Self {
+        if symmetric_difference_swaps(&self, &other) {
+            other.symmetric_difference_with(&self);
+            other
+        } else {
+            self.symmetric_difference_with(&other);
+            self
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match *self {
+            Array(ref vec) => vec.is_empty(),
+            Bitmap(ref bits) => bits.iter().all(|&word| word == 0),
+            Run(ref runs) => runs.is_empty(),
         }
     }
 
@@ -295,6 +804,7 @@ impl<Size: ExtInt> Store<Size> {
                 }
                 util::cast(len)
             },
+            Run(ref runs) => runs_len(runs),
         }
     }
 
@@ -307,6 +817,7 @@ impl<Size: ExtInt> Store<Size> {
                     .next().map(|(index, bit)| util::cast(index * 64 + (bit.trailing_zeros() as usize)))
                     .unwrap()
             },
+            Run(ref runs) => runs.first().unwrap().0,
         }
     }
 
@@ -319,14 +830,131 @@ impl<Size: ExtInt> Store<Size> {
                     .next().map(|(index, bit)| util::cast(index * 64 + (63 - (bit.leading_zeros() as usize))))
                     .unwrap()
             },
+            Run(ref runs) => {
+                let &(start, length) = runs.last().unwrap();
+                start + length
+            },
+        }
+    }
+
+    // Returns the smallest value strictly greater than `index`.
+    pub fn successor(&self, index: Size) -> Option<Size> {
+        if index == Bounded::max_value() {
+            return None;
+        }
+        match *self {
+            Array(ref vec) => {
+                let loc = vec.binary_search(&index).map(|loc| loc + 1).unwrap_or_else(|loc| loc);
+                vec.get(loc).cloned()
+            },
+            Bitmap(ref bits) => {
+                let (key, bit) = bitmap_location(index + One::one());
+                let first = bits[key] & (!0u64 << bit);
+                if first != 0 {
+                    return Some(util::cast(key as u64 * 64 + first.trailing_zeros() as u64));
+                }
+                bits[key + 1..].iter().enumerate()
+                    .find(|&(_, &word)| word != 0)
+                    .map(|(offset, word)| util::cast((key + 1 + offset) as u64 * 64 + word.trailing_zeros() as u64))
+            },
+            Run(ref runs) => match run_search(runs, index) {
+                Ok(loc) if index.to64() < run_end(&runs[loc]) => Some(index + One::one()),
+                Ok(loc) => runs.get(loc + 1).map(|run| run.0),
+                Err(loc) => runs.get(loc).map(|run| run.0),
+            },
+        }
+    }
+
+    // Returns the largest value strictly less than `index`.
+    pub fn predecessor(&self, index: Size) -> Option<Size> {
+        if index == Zero::zero() {
+            return None;
+        }
+        match *self {
+            Array(ref vec) => {
+                let loc = vec.binary_search(&index).unwrap_or_else(|loc| loc);
+                if loc == 0 { None } else { Some(vec[loc - 1]) }
+            },
+            Bitmap(ref bits) => {
+                let (key, bit) = bitmap_location(index - One::one());
+                let last = bits[key] & (!0u64 >> (63 - bit));
+                if last != 0 {
+                    return Some(util::cast(key as u64 * 64 + 63 - last.leading_zeros() as u64));
+                }
+                bits[..key].iter().enumerate().rev()
+                    .find(|&(_, &word)| word != 0)
+                    .map(|(offset, word)| util::cast(offset as u64 * 64 + 63 - word.leading_zeros() as u64))
+            },
+            Run(ref runs) => match run_search(runs, index) {
+                Ok(loc) if index > runs[loc].0 => Some(index - One::one()),
+                Ok(loc) | Err(loc) => {
+                    if loc == 0 { None } else { Some(util::cast(run_end(&runs[loc - 1]))) }
+                },
+            },
+        }
+    }
+
+    // Returns the number of values less than or equal to `index`. Bitmap and run stores count
+    // the words or runs before `index`, at most 1024 and 2048 of them.
+    pub fn rank(&self, index: Size) -> u64 {
+        match *self {
+            Array(ref vec) => vec.binary_search(&index).map(|loc| loc + 1).unwrap_or_else(|loc| loc) as u64,
+            Bitmap(ref bits) => count_range(bits, 0, index.to64()),
+            Run(ref runs) => match run_search(runs, index) {
+                Ok(loc) => runs_len(&runs[..loc]) + index.to64() - runs[loc].0.to64() + 1,
+                Err(loc) => runs_len(&runs[..loc]),
+            },
+        }
+    }
+
+    // Returns the `n`th smallest value, counting from zero, if there are more than `n` values.
+    pub fn select(&self, n: u64) -> Option<Size> {
+        match *self {
+            Array(ref vec) => vec.get(n as usize).cloned(),
+            Bitmap(ref bits) => {
+                let mut remaining = n;
+                for (key, &word) in bits.iter().enumerate() {
+                    let count = word.count_ones() as u64;
+                    if remaining < count {
+                        let mut word = word;
+                        for _ in 0..remaining {
+                            word &= word - 1;
+                        }
+                        return Some(util::cast(key as u64 * 64 + word.trailing_zeros() as u64));
+                    }
+                    remaining -= count;
+                }
+                None
+            },
+            Run(ref runs) => {
+                let mut remaining = n;
+                for run in runs {
+                    let count = run.1.to64() + 1;
+                    if remaining < count {
+                        return Some(util::cast(run.0.to64() + remaining));
+                    }
+                    remaining -= count;
+                }
+                None
+            },
+        }
+    }
+
+    #[inline]
+    pub fn into_iter(self) -> IntoStoreIter<Size> {
+        match self {
+            Array(vec) => IntoStoreIter::Array(vec.into_iter()),
+            Bitmap(bits) => IntoStoreIter::Bitmap(BitmapIter::new(bits)),
+            Run(runs) => IntoStoreIter::Run(RunIter::new(runs)),
         }
     }
 
     #[inline]
-    pub fn iter<'a>(&'a self) -> Box<Iterator<Item = Size> + 'a> {
+    pub fn iter<'a>(&'a self) -> StoreIter<'a, Size> {
         match *self {
-            Array(ref vec) => Box::new(vec.iter().map(|x| *x)),
-            Bitmap(ref bits) => Box::new(BitmapIter::new(bits)),
+            Array(ref vec) => StoreIter::Array(vec.iter()),
+            Bitmap(ref bits) => StoreIter::Bitmap(BitmapIter::new(&bits[..])),
+            Run(ref runs) => StoreIter::Run(RunIter::new(&runs[..])),
         }
     }
 
@@ -341,7 +969,10 @@ impl<Size: ExtInt> PartialEq for Store<Size> {
             (&Bitmap(ref bits1), &Bitmap(ref bits2)) => {
                 bits1.iter().zip(bits2.iter()).all(|(i1, i2)| i1 == i2)
             },
-            _ => false,
+            (&Run(ref runs1), &Run(ref runs2)) => {
+                runs1 == runs2
+            },
+            _ => self.len() == other.len() && self.iter().eq(other.iter()),
         }
     }
 }
@@ -353,62 +984,478 @@ impl<Size: ExtInt> Clone for Store<Size> {
             Bitmap(ref bits) => {
                 Bitmap(bits.iter().map(|&i| i).collect::<Vec<u64>>().into_boxed_slice())
             },
+            Run(ref runs) => Run(runs.clone()),
+        }
+    }
+}
+
+pub enum StoreIter<'a, Size: ExtInt + 'a> {
+    Array(slice::Iter<'a, Size>),
+    Bitmap(BitmapIter<&'a [u64], Size>),
+    Run(RunIter<&'a [(Size, Size)], Size>),
+}
+
+impl<'a, Size: ExtInt> Iterator for StoreIter<'a, Size> {
+    type Item = Size;
+
+    #[inline]
+    fn next(&mut self) -> Option<Size> {
+        match *self {
+            StoreIter::Array(ref mut iter) => iter.next().cloned(),
+            StoreIter::Bitmap(ref mut iter) => iter.next(),
+            StoreIter::Run(ref mut iter) => iter.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match *self {
+            StoreIter::Array(ref iter) => iter.size_hint(),
+            StoreIter::Bitmap(ref iter) => iter.size_hint(),
+            StoreIter::Run(ref iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<'a, Size: ExtInt> DoubleEndedIterator for StoreIter<'a, Size> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Size> {
+        match *self {
+            StoreIter::Array(ref mut iter) => iter.next_back().cloned(),
+            StoreIter::Bitmap(ref mut iter) => iter.next_back(),
+            StoreIter::Run(ref mut iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<'a, Size: ExtInt> StoreIter<'a, Size> {
+    // Skips forward to the first remaining value not less than `index`.
+    pub fn advance_to(&mut self, index: Size) {
+        match *self {
+            StoreIter::Array(ref mut iter) => {
+                let slice = iter.as_slice();
+                let loc = slice.binary_search(&index).unwrap_or_else(|loc| loc);
+                *iter = slice[loc..].iter();
+            },
+            StoreIter::Bitmap(ref mut iter) => iter.advance_to(index),
+            StoreIter::Run(ref mut iter) => iter.advance_to(index),
+        }
+    }
+
+    // Fills `out` from the front with the values mapped through `f`, returning how many were written.
+    pub fn next_many<T, F: Fn(Size) -> T>(&mut self, out: &mut [T], f: F) -> usize {
+        match *self {
+            StoreIter::Array(ref mut iter) => {
+                let slice = iter.as_slice();
+                let count = cmp::min(slice.len(), out.len());
+                for (slot, &value) in out.iter_mut().zip(&slice[..count]) {
+                    *slot = f(value);
+                }
+                *iter = slice[count..].iter();
+                count
+            },
+            StoreIter::Bitmap(ref mut iter) => iter.next_many(out, f),
+            StoreIter::Run(ref mut iter) => iter.next_many(out, f),
+        }
+    }
+
+    // Skips backward to the last remaining value not greater than `index`.
+    pub fn advance_back_to(&mut self, index: Size) {
+        match *self {
+            StoreIter::Array(ref mut iter) => {
+                let slice = iter.as_slice();
+                let loc = slice.binary_search(&index).map(|loc| loc + 1).unwrap_or_else(|loc| loc);
+                *iter = slice[..loc].iter();
+            },
+            StoreIter::Bitmap(ref mut iter) => iter.advance_back_to(index),
+            StoreIter::Run(ref mut iter) => iter.advance_back_to(index),
+        }
+    }
+}
+
+impl<'a, Size: ExtInt> ExactSizeIterator for StoreIter<'a, Size> { }
+
+pub enum IntoStoreIter<Size: ExtInt> {
+    Array(vec::IntoIter<Size>),
+    Bitmap(BitmapIter<Box<[u64]>, Size>),
+    Run(RunIter<Vec<(Size, Size)>, Size>),
+}
+
+impl<Size: ExtInt> Iterator for IntoStoreIter<Size> {
+    type Item = Size;
+
+    #[inline]
+    fn next(&mut self) -> Option<Size> {
+        match *self {
+            IntoStoreIter::Array(ref mut iter) => iter.next(),
+            IntoStoreIter::Bitmap(ref mut iter) => iter.next(),
+            IntoStoreIter::Run(ref mut iter) => iter.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match *self {
+            IntoStoreIter::Array(ref iter) => iter.size_hint(),
+            IntoStoreIter::Bitmap(ref iter) => iter.size_hint(),
+            IntoStoreIter::Run(ref iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<Size: ExtInt> DoubleEndedIterator for IntoStoreIter<Size> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Size> {
+        match *self {
+            IntoStoreIter::Array(ref mut iter) => iter.next_back(),
+            IntoStoreIter::Bitmap(ref mut iter) => iter.next_back(),
+            IntoStoreIter::Run(ref mut iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<Size: ExtInt> IntoStoreIter<Size> {
+    // Skips forward to the first remaining value not less than `index`.
+    pub fn advance_to(&mut self, index: Size) {
+        match *self {
+            IntoStoreIter::Array(ref mut iter) => {
+                let loc = iter.as_slice().binary_search(&index).unwrap_or_else(|loc| loc);
+                if loc > 0 {
+                    iter.nth(loc - 1);
+                }
+            },
+            IntoStoreIter::Bitmap(ref mut iter) => iter.advance_to(index),
+            IntoStoreIter::Run(ref mut iter) => iter.advance_to(index),
         }
     }
 }
 
-struct BitmapIter<'a, Size: ExtInt> {
+impl<Size: ExtInt> ExactSizeIterator for IntoStoreIter<Size> { }
+
+// The front and back cursors each keep their own copy of the word they are in. They can't
+// return the same value twice, as between them they only return `remaining` values.
+pub struct BitmapIter<B: Borrow<[u64]>, Size: ExtInt> {
     key: usize,
-    bit: u8,
-    bits: &'a Box<[u64]>,
+    // The bits of `bits[key]` that haven't been returned from the front yet.
+    word: u64,
+    key_back: usize,
+    // The bits of `bits[key_back]` that haven't been returned from the back yet.
+    word_back: u64,
+    bits: B,
+    remaining: u64,
     marker: PhantomData<Size>,
 }
 
-impl<'a, Size: ExtInt> BitmapIter<'a, Size> {
-    fn new(bits: &'a Box<[u64]>) -> BitmapIter<'a, Size> {
+impl<B: Borrow<[u64]>, Size: ExtInt> BitmapIter<B, Size> {
+    fn new(bits: B) -> BitmapIter<B, Size> {
+        let (word, word_back, remaining) = {
+            let words = bits.borrow();
+            (words[0], words[words.len() - 1], words.iter().map(|word| word.count_ones() as u64).sum())
+        };
         BitmapIter {
             key: 0,
-            bit: Zero::zero(),
+            word: word,
+            key_back: bits.borrow().len() - 1,
+            word_back: word_back,
             bits: bits,
+            remaining: remaining,
             marker: PhantomData,
         }
     }
-}
 
+    // Returns the value `next` would, without moving the cursor.
+    fn peek(&self) -> Option<u64> {
+        if self.remaining == 0 {
+            None
+        } else if self.word != 0 {
+            Some(self.key as u64 * 64 + self.word.trailing_zeros() as u64)
+        } else {
+            let bits = self.bits.borrow();
+            bits[self.key + 1..].iter().position(|&word| word != 0)
+                .map(|offset| (self.key + 1 + offset) as u64 * 64 + bits[self.key + 1 + offset].trailing_zeros() as u64)
+        }
+    }
 
-impl<'a, Size: ExtInt> BitmapIter<'a, Size> {
-    fn move_next(&mut self) {
-        self.bit += 1;
-        if self.bit == 64 {
-            self.bit = 0;
-            self.key += 1;
+    // Returns the value `next_back` would, without moving the cursor.
+    fn peek_back(&self) -> Option<u64> {
+        if self.remaining == 0 {
+            None
+        } else if self.word_back != 0 {
+            Some(self.key_back as u64 * 64 + 63 - self.word_back.leading_zeros() as u64)
+        } else {
+            let bits = self.bits.borrow();
+            bits[..self.key_back].iter().rposition(|&word| word != 0)
+                .map(|key| key as u64 * 64 + 63 - bits[key].leading_zeros() as u64)
+        }
+    }
+
+    fn advance_to(&mut self, index: Size) {
+        let target = index.to64();
+        match self.peek_back() {
+            None => return,
+            Some(max) if max < target => {
+                self.remaining = 0;
+                return;
+            },
+            // Anything skipped is below a value the back cursor hasn't reached, so it's still counted.
+            Some(_) => (),
         }
+        let (key, bit) = ((target / 64) as usize, target % 64);
+        if key < self.key {
+            return;
+        }
+        let mut skipped = 0;
+        if key > self.key {
+            skipped += self.word.count_ones() as u64;
+            skipped += self.bits.borrow()[self.key + 1..key].iter().map(|word| word.count_ones() as u64).sum::<u64>();
+            self.key = key;
+            self.word = self.bits.borrow()[key];
+        }
+        skipped += (self.word & !(!0u64 << bit)).count_ones() as u64;
+        self.word &= !0u64 << bit;
+        self.remaining -= skipped;
+    }
+
+    fn next_many<T, F: Fn(Size) -> T>(&mut self, out: &mut [T], f: F) -> usize {
+        let count = cmp::min(self.remaining, out.len() as u64) as usize;
+        let mut written = 0;
+        while written < count {
+            while self.word == 0 {
+                self.key += 1;
+                self.word = self.bits.borrow()[self.key];
+            }
+            let base = self.key as u64 * 64;
+            while self.word != 0 && written < count {
+                out[written] = f(util::cast(base + self.word.trailing_zeros() as u64));
+                self.word &= self.word - 1;
+                written += 1;
+            }
+        }
+        self.remaining -= count as u64;
+        count
     }
 
+    fn advance_back_to(&mut self, index: Size) {
+        let target = index.to64();
+        match self.peek() {
+            None => return,
+            Some(min) if min > target => {
+                self.remaining = 0;
+                return;
+            },
+            Some(_) => (),
+        }
+        let (key, bit) = ((target / 64) as usize, target % 64);
+        if key > self.key_back {
+            return;
+        }
+        let mut skipped = 0;
+        if key < self.key_back {
+            skipped += self.word_back.count_ones() as u64;
+            skipped += self.bits.borrow()[key + 1..self.key_back].iter().map(|word| word.count_ones() as u64).sum::<u64>();
+            self.key_back = key;
+            self.word_back = self.bits.borrow()[key];
+        }
+        skipped += (self.word_back & !(!0u64 >> (63 - bit))).count_ones() as u64;
+        self.word_back &= !0u64 >> (63 - bit);
+        self.remaining -= skipped;
+    }
 }
 
-impl<'a, Size: ExtInt> Iterator for BitmapIter<'a, Size> {
+impl<B: Borrow<[u64]>, Size: ExtInt> Iterator for BitmapIter<B, Size> {
     type Item = Size;
 
     fn next(&mut self) -> Option<Size> {
+        if self.remaining == 0 {
+            return None;
+        }
+        while self.word == 0 {
+            self.key += 1;
+            self.word = self.bits.borrow()[self.key];
+        }
+        let bit = self.word.trailing_zeros() as u64;
+        self.word &= self.word - 1;
+        self.remaining -= 1;
+        Some(util::cast(self.key as u64 * 64 + bit))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+impl<B: Borrow<[u64]>, Size: ExtInt> DoubleEndedIterator for BitmapIter<B, Size> {
+    fn next_back(&mut self) -> Option<Size> {
+        if self.remaining == 0 {
+            return None;
+        }
+        while self.word_back == 0 {
+            self.key_back -= 1;
+            self.word_back = self.bits.borrow()[self.key_back];
+        }
+        let bit = 63 - self.word_back.leading_zeros() as u64;
+        self.word_back &= !(1 << bit);
+        self.remaining -= 1;
+        Some(util::cast(self.key_back as u64 * 64 + bit))
+    }
+}
+
+pub struct RunIter<R: Borrow<[(Size, Size)]>, Size: ExtInt> {
+    runs: R,
+    // The run holding the next value from the front, and that value.
+    run: usize,
+    next: u64,
+    // The run holding the next value from the back, and one past that value.
+    run_back: usize,
+    next_back: u64,
+    remaining: u64,
+    marker: PhantomData<Size>,
+}
+
+impl<R: Borrow<[(Size, Size)]>, Size: ExtInt> RunIter<R, Size> {
+    fn new(runs: R) -> RunIter<R, Size> {
+        let (next, run_back, next_back, remaining) = {
+            let slice = runs.borrow();
+            (
+                slice.first().map_or(0, |run| run.0.to64()),
+                slice.len().saturating_sub(1),
+                slice.last().map_or(0, |run| run_end(run) + 1),
+                runs_len(slice),
+            )
+        };
+        RunIter {
+            runs: runs,
+            run: 0,
+            next: next,
+            run_back: run_back,
+            next_back: next_back,
+            remaining: remaining,
+            marker: PhantomData,
+        }
+    }
+
+    fn advance_to(&mut self, index: Size) {
+        let target = index.to64();
+        if self.remaining == 0 || target <= self.next {
+            return;
+        }
+        if target >= self.next_back {
+            self.remaining = 0;
+            return;
+        }
+        // The target is below the back cursor, so nothing skipped has been returned from the back.
+        let mut skipped = 0;
         loop {
-            if self.key == self.bits.len() {
-                return None;
-            } else {
-                if (self.bits[self.key] & (1u64 << util::cast::<u8, usize>(self.bit))) != 0 {
-                    let result = Some(util::cast::<usize, Size>(self.key * 64 + util::cast::<u8, usize>(self.bit)));
-                    self.move_next();
-                    return result;
-                } else {
-                    self.move_next();
+            let end = run_end(&self.runs.borrow()[self.run]);
+            if target <= end {
+                if self.next < target {
+                    skipped += target - self.next;
+                    self.next = target;
+                }
+                break;
+            }
+            if self.next <= end {
+                skipped += end - self.next + 1;
+            }
+            self.run += 1;
+            self.next = self.runs.borrow()[self.run].0.to64();
+            if self.next >= target {
+                break;
+            }
+        }
+        self.remaining -= skipped;
+    }
+
+    fn next_many<T, F: Fn(Size) -> T>(&mut self, out: &mut [T], f: F) -> usize {
+        let count = cmp::min(self.remaining, out.len() as u64) as usize;
+        let mut written = 0;
+        while written < count {
+            let mut end = run_end(&self.runs.borrow()[self.run]);
+            if self.next > end {
+                self.run += 1;
+                self.next = self.runs.borrow()[self.run].0.to64();
+                end = run_end(&self.runs.borrow()[self.run]);
+            }
+            let take = cmp::min(end - self.next + 1, (count - written) as u64);
+            for (slot, value) in out[written..].iter_mut().zip(self.next..(self.next + take)) {
+                *slot = f(util::cast(value));
+            }
+            self.next += take;
+            written += take as usize;
+        }
+        self.remaining -= count as u64;
+        count
+    }
+
+    fn advance_back_to(&mut self, index: Size) {
+        let target = index.to64();
+        if self.remaining == 0 || target + 1 >= self.next_back {
+            return;
+        }
+        if target < self.next {
+            self.remaining = 0;
+            return;
+        }
+        // The target is above the front cursor, so nothing skipped has been returned from the front.
+        let mut skipped = 0;
+        loop {
+            let start = self.runs.borrow()[self.run_back].0.to64();
+            if target >= start {
+                if self.next_back > target + 1 {
+                    skipped += self.next_back - (target + 1);
+                    self.next_back = target + 1;
                 }
+                break;
+            }
+            if self.next_back > start {
+                skipped += self.next_back - start;
             }
+            self.run_back -= 1;
+            self.next_back = run_end(&self.runs.borrow()[self.run_back]) + 1;
+            if self.next_back <= target + 1 {
+                break;
+            }
+        }
+        self.remaining -= skipped;
+    }
+}
+
+impl<R: Borrow<[(Size, Size)]>, Size: ExtInt> Iterator for RunIter<R, Size> {
+    type Item = Size;
+
+    fn next(&mut self) -> Option<Size> {
+        if self.remaining == 0 {
+            return None;
+        }
+        if self.next > run_end(&self.runs.borrow()[self.run]) {
+            self.run += 1;
+            self.next = self.runs.borrow()[self.run].0.to64();
         }
+        let value = self.next;
+        self.next += 1;
+        self.remaining -= 1;
+        Some(util::cast(value))
     }
 
+    #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-      let min = self.bits.iter().skip(self.key + 1).map(|bits| bits.count_ones()).fold(0, |acc, ones| acc + ones) as usize;
-      (min, Some(min + self.bits[self.key].count_ones() as usize))
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+impl<R: Borrow<[(Size, Size)]>, Size: ExtInt> DoubleEndedIterator for RunIter<R, Size> {
+    fn next_back(&mut self) -> Option<Size> {
+        if self.remaining == 0 {
+            return None;
+        }
+        if self.next_back == self.runs.borrow()[self.run_back].0.to64() {
+            self.run_back -= 1;
+            self.next_back = run_end(&self.runs.borrow()[self.run_back]) + 1;
+        }
+        self.next_back -= 1;
+        self.remaining -= 1;
+        Some(util::cast(self.next_back))
     }
 }
 
@@ -420,3 +1467,175 @@ fn key<Size: ExtInt>(index: Size) -> usize { util::cast(index / util::cast(64u8)
 
 #[inline]
 fn bit<Size: ExtInt>(index: Size) -> usize { util::cast(index % util::cast(64u8)) }
+
+#[inline]
+pub fn capacity<Size: ExtInt>() -> u64 { util::cast::<Size, u64>(Bounded::max_value()) + 1 }
+
+#[inline]
+pub fn empty_bitmap<Size: ExtInt>() -> Box<[u64]> {
+    vec![0; (capacity::<Size>() / 64) as usize].into_boxed_slice()
+}
+
+#[inline]
+fn run_end<Size: ExtInt>(run: &(Size, Size)) -> u64 { run.0.to64() + run.1.to64() }
+
+#[inline]
+fn runs_len<Size: ExtInt>(runs: &[(Size, Size)]) -> u64 { runs.iter().map(|run| run.1.to64() + 1).sum() }
+
+// Finds the run containing `index`, or the location a new run starting at `index` would go.
+fn run_search<Size: ExtInt>(runs: &[(Size, Size)], index: Size) -> Result<usize, usize> {
+    match runs.binary_search_by(|run| run.0.cmp(&index)) {
+        Ok(loc) => Ok(loc),
+        Err(0) => Err(0),
+        Err(loc) => if index.to64() <= run_end(&runs[loc - 1]) { Ok(loc - 1) } else { Err(loc) },
+    }
+}
+
+fn array_to_runs<Size: ExtInt>(vec: &[Size]) -> Vec<(Size, Size)> {
+    let mut runs: Vec<(Size, Size)> = Vec::new();
+    for &index in vec.iter() {
+        match runs.last_mut() {
+            Some(run) if run.0 + run.1 + One::one() == index => run.1 = run.1 + One::one(),
+            _ => runs.push((index, Zero::zero())),
+        }
+    }
+    runs
+}
+
+// Appends the inclusive interval `start...end` to `runs`, merging it with the last run if adjacent.
+fn push_run<Size: ExtInt>(runs: &mut Vec<(Size, Size)>, start: u64, end: u64) {
+    if let Some(run) = runs.last_mut() {
+        if run_end(run) + 1 == start {
+            run.1 = util::cast(end - run.0.to64());
+            return;
+        }
+    }
+    runs.push((util::cast(start), util::cast(end - start)));
+}
+
+// Merges two sorted arrays, keeping the values found only in the first, in both, or only in the
+// second as asked.
+fn array_op<Size: ExtInt>(vec1: &[Size], vec2: &[Size], only1: bool, both: bool, only2: bool) -> Vec<Size> {
+    let mut vec = Vec::new();
+    let (mut i1, mut i2) = (0, 0);
+    while i1 < vec1.len() && i2 < vec2.len() {
+        match vec1[i1].cmp(&vec2[i2]) {
+            Less => {
+                if only1 { vec.push(vec1[i1]); }
+                i1 += 1;
+            },
+            Greater => {
+                if only2 { vec.push(vec2[i2]); }
+                i2 += 1;
+            },
+            Equal => {
+                if both { vec.push(vec1[i1]); }
+                i1 += 1;
+                i2 += 1;
+            },
+        }
+    }
+    if only1 { vec.extend_from_slice(&vec1[i1..]); }
+    if only2 { vec.extend_from_slice(&vec2[i2..]); }
+    vec
+}
+
+// Combines the two stores as bitmaps, word by word.
+fn words_op<Size: ExtInt, F: Fn(u64, u64) -> u64>(this: &Store<Size>, other: &Store<Size>, op: F) -> Store<Size> {
+    let mut bits = this.words();
+    let scratch;
+    let bits2 = match *other {
+        Bitmap(ref bits2) => bits2,
+        _ => {
+            scratch = other.words();
+            &scratch
+        },
+    };
+    for (word1, &word2) in bits.iter_mut().zip(bits2.iter()) {
+        *word1 = op(*word1, word2);
+    }
+    Bitmap(bits)
+}
+
+// Whether a union is cheaper run in place on `other`, so `this` is the one merged in.
+fn union_swaps<Size: ExtInt>(this: &Store<Size>, other: &Store<Size>) -> bool {
+    match (this, other) {
+        (&Array(ref vec1), &Array(ref vec2)) => vec1.len() < vec2.len(),
+        (&Array(..), &Run(..)) | (&Array(..), &Bitmap(..)) | (&Run(..), &Bitmap(..)) => true,
+        _ => false,
+    }
+}
+
+// Whether a symmetric difference is cheaper run in place on `other`.
+fn symmetric_difference_swaps<Size: ExtInt>(this: &Store<Size>, other: &Store<Size>) -> bool {
+    match (this, other) {
+        (&Array(..), &Bitmap(..)) | (&Array(..), &Run(..)) | (&Run(..), &Bitmap(..)) => true,
+        _ => false,
+    }
+}
+
+// Sweeps both run lists together, keeping every value for which `op(in runs1, in runs2)` holds.
+fn run_op<Size: ExtInt, F: Fn(bool, bool) -> bool>(runs1: &[(Size, Size)], runs2: &[(Size, Size)], op: F) -> Vec<(Size, Size)> {
+    // Returns whether `position` is inside the current run and where that next changes.
+    fn state<Size: ExtInt>(runs: &[(Size, Size)], i: &mut usize, position: u64) -> (bool, Option<u64>) {
+        while *i < runs.len() && run_end(&runs[*i]) < position {
+            *i += 1;
+        }
+        match runs.get(*i) {
+            Some(run) if run.0.to64() <= position => (true, Some(run_end(run) + 1)),
+            Some(run) => (false, Some(run.0.to64())),
+            None => (false, None),
+        }
+    }
+
+    let mut result = Vec::new();
+    let (mut i1, mut i2, mut position) = (0, 0, 0);
+    loop {
+        let (in1, next1) = state(runs1, &mut i1, position);
+        let (in2, next2) = state(runs2, &mut i2, position);
+        let next = match (next1, next2) {
+            (None, None) => return result,
+            (Some(n1), Some(n2)) => cmp::min(n1, n2),
+            (n1, n2) => n1.or(n2).unwrap(),
+        };
+        if op(in1, in2) {
+            push_run(&mut result, position, next - 1);
+        }
+        position = next;
+    }
+}
+
+#[inline]
+fn range_mask(key: usize, start: u64, end: u64) -> u64 {
+    let low = if key == (start / 64) as usize { start % 64 } else { 0 };
+    let high = if key == (end / 64) as usize { end % 64 } else { 63 };
+    (!0u64 >> (63 - high)) & (!0u64 << low)
+}
+
+// The `*_range` helpers operate on the inclusive range `start...end`.
+pub fn set_range(bits: &mut [u64], start: u64, end: u64) {
+    let first = (start / 64) as usize;
+    for (key, word) in bits[first..((end / 64) as usize + 1)].iter_mut().enumerate() {
+        *word |= range_mask(first + key, start, end);
+    }
+}
+
+pub fn clear_range(bits: &mut [u64], start: u64, end: u64) {
+    let first = (start / 64) as usize;
+    for (key, word) in bits[first..((end / 64) as usize + 1)].iter_mut().enumerate() {
+        *word &= !range_mask(first + key, start, end);
+    }
+}
+
+pub fn flip_range(bits: &mut [u64], start: u64, end: u64) {
+    let first = (start / 64) as usize;
+    for (key, word) in bits[first..((end / 64) as usize + 1)].iter_mut().enumerate() {
+        *word ^= range_mask(first + key, start, end);
+    }
+}
+
+pub fn count_range(bits: &[u64], start: u64, end: u64) -> u64 {
+    ((start / 64) as usize..((end / 64) as usize + 1))
+        .map(|key| (bits[key] & range_mask(key, start, end)).count_ones() as u64)
+        .sum()
+}
@@ -0,0 +1,1385 @@
+use std::u32;
+use std::io;
+use std::collections::btree_map::{ self, BTreeMap, Entry };
+use std::fmt::{ Debug, Formatter, Result };
+use std::ops::{ BitXor, BitAnd, BitOr, Sub, RangeBounds };
+use std::iter::{ IntoIterator, FromIterator, Peekable };
+
+use imp;
+use iter::{ Iter, IntoIter, UnionIter, IntersectionIter, DifferenceIter, SymmetricDifferenceIter };
+use util::{ self, Halveable };
+use error::DeserializeError;
+use serialization;
+
+use RoaringBitmap;
+
+/// A compressed bitmap for 64-bit values.
+///
+/// Values are split into their high and low 32 bits, the high bits select a `RoaringBitmap<u32>`
+/// that stores the low bits. Unlike `RoaringBitmap<u64>` this never needs a single container
+/// covering 2<sup>32</sup> values.
+///
+/// # Examples
+///
+/// ```rust
+/// use roaring::RoaringTreemap;
+///
+/// let mut rt = RoaringTreemap::new();
+///
+/// // insert all primes less than 10
+/// rt.insert(2);
+/// rt.insert(3);
+/// rt.insert(5);
+/// rt.insert(7);
+/// println!("total bits set to true: {}", rt.len());
+/// ```
+#[derive(PartialEq, Clone)]
+pub struct RoaringTreemap {
+    map: BTreeMap<u32, RoaringBitmap<u32>>,
+}
+
+impl RoaringTreemap {
+    /// Creates an empty `RoaringTreemap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    /// let mut rt = RoaringTreemap::new();
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        RoaringTreemap { map: BTreeMap::new() }
+    }
+
+    /// Creates a `RoaringTreemap` from the output of `to_raw64`.
+    ///
+    /// # Panics
+    ///
+    /// If `bits` is not a valid serialized treemap, see `try_from_raw64` for a non-panicking
+    /// version.
+    #[inline]
+    pub fn from_raw64(bits: Vec<u64>) -> Self {
+        match RoaringTreemap::try_from_raw64(&bits) {
+            Ok(rt) => rt,
+            Err(error) => panic!("invalid raw64 treemap: {}", error),
+        }
+    }
+
+    /// Creates a `RoaringTreemap` from the output of `to_raw64`, checking that `bits` is complete
+    /// and describes a valid treemap.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt: RoaringTreemap = (1..4u64).collect();
+    /// let mut raw64 = rt.to_raw64();
+    ///
+    /// assert_eq!(RoaringTreemap::try_from_raw64(&raw64).unwrap(), rt);
+    ///
+    /// raw64.push(0);
+    /// assert!(RoaringTreemap::try_from_raw64(&raw64).is_err());
+    /// ```
+    pub fn try_from_raw64(bits: &[u64]) -> ::std::result::Result<Self, DeserializeError> {
+        let mut rt = RoaringTreemap::new();
+        let num_bitmaps = match bits.first() {
+            Some(&num_bitmaps) if num_bitmaps <= bits.len() as u64 => num_bitmaps,
+            _ => return Err(DeserializeError::UnexpectedEnd),
+        };
+        let mut next = 1;
+        for _ in 0..num_bitmaps {
+            let key = match bits.get(next) {
+                Some(&key) if key > u32::MAX as u64 => return Err(DeserializeError::ValueOutOfRange(key)),
+                Some(&key) => key as u32,
+                None => return Err(DeserializeError::UnexpectedEnd),
+            };
+            if rt.map.keys().next_back().map_or(false, |&last| last >= key) {
+                return Err(DeserializeError::UnsortedKeys);
+            }
+            let (bitmap, end) = imp::try_from_raw64_at(bits, next + 1)?;
+            if bitmap.is_empty() {
+                return Err(DeserializeError::EmptyContainer);
+            }
+            rt.map.insert(key, bitmap);
+            next = end;
+        }
+        if next != bits.len() {
+            return Err(DeserializeError::TrailingData);
+        }
+        Ok(rt)
+    }
+
+    /// Adds a value to the set. Returns `true` if the value was not already present in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt = RoaringTreemap::new();
+    /// assert_eq!(rt.insert(3), true);
+    /// assert_eq!(rt.insert(3), false);
+    /// assert_eq!(rt.contains(3), true);
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, value: u64) -> bool {
+        let (hi, lo) = value.split();
+        self.map.entry(hi).or_insert_with(RoaringBitmap::new).insert(lo)
+    }
+
+    /// Removes a value from the set. Returns `true` if the value was present in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt = RoaringTreemap::new();
+    /// rt.insert(3);
+    /// assert_eq!(rt.remove(3), true);
+    /// assert_eq!(rt.remove(3), false);
+    /// assert_eq!(rt.contains(3), false);
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, value: u64) -> bool {
+        let (hi, lo) = value.split();
+        match self.map.entry(hi) {
+            Entry::Vacant(_) => false,
+            Entry::Occupied(mut entry) => {
+                if entry.get_mut().remove(lo) {
+                    if entry.get().is_empty() {
+                        entry.remove();
+                    }
+                    true
+                } else {
+                    false
+                }
+            },
+        }
+    }
+
+    /// Inserts every value in `range` into the set. Returns the number of values that were not
+    /// already present.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt = RoaringTreemap::new();
+    /// rt.insert(5);
+    /// assert_eq!(rt.insert_range(2..10), 7);
+    /// assert_eq!(rt.insert_range((1 << 32) - 2..(1 << 32) + 2), 4);
+    /// assert_eq!(rt.contains(9), true);
+    /// assert_eq!(rt.contains(1 << 32), true);
+    /// ```
+    pub fn insert_range<R: RangeBounds<u64>>(&mut self, range: R) -> u128 {
+        let (start, end) = match util::convert_range(range) {
+            Some(bounds) => bounds,
+            None => return 0,
+        };
+        let (start_key, start_index) = start.split();
+        let (end_key, end_index) = end.split();
+        let mut inserted = 0;
+        for key in start_key..=end_key {
+            let lo = if key == start_key { start_index } else { 0 };
+            let hi = if key == end_key { end_index } else { u32::MAX };
+            inserted += self.map.entry(key).or_insert_with(RoaringBitmap::new).insert_range(lo..=hi) as u128;
+        }
+        inserted
+    }
+
+    /// Removes every value in `range` from the set. Returns the number of values that were
+    /// present.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt = RoaringTreemap::new();
+    /// rt.insert_range(2..10);
+    /// rt.insert(1 << 40);
+    /// assert_eq!(rt.remove_range(5..=1 << 40), 6);
+    /// assert_eq!(rt.remove_range(5..=1 << 40), 0);
+    /// assert_eq!(rt.contains(4), true);
+    /// assert_eq!(rt.contains(1 << 40), false);
+    /// ```
+    pub fn remove_range<R: RangeBounds<u64>>(&mut self, range: R) -> u128 {
+        let (start, end) = match util::convert_range(range) {
+            Some(bounds) => bounds,
+            None => return 0,
+        };
+        let (start_key, start_index) = start.split();
+        let (end_key, end_index) = end.split();
+        let mut removed = 0;
+        let mut emptied = Vec::new();
+        for (&key, bitmap) in self.map.range_mut(start_key..=end_key) {
+            let lo = if key == start_key { start_index } else { 0 };
+            let hi = if key == end_key { end_index } else { u32::MAX };
+            removed += bitmap.remove_range(lo..=hi) as u128;
+            if bitmap.is_empty() {
+                emptied.push(key);
+            }
+        }
+        for key in emptied {
+            self.map.remove(&key);
+        }
+        removed
+    }
+
+    /// Returns `true` if this set contains the specified integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt = RoaringTreemap::new();
+    /// rt.insert(1);
+    /// assert_eq!(rt.contains(0), false);
+    /// assert_eq!(rt.contains(1), true);
+    /// assert_eq!(rt.contains(100), false);
+    /// ```
+    #[inline]
+    pub fn contains(&self, value: u64) -> bool {
+        let (hi, lo) = value.split();
+        match self.map.get(&hi) {
+            Some(bitmap) => bitmap.contains(lo),
+            None => false,
+        }
+    }
+
+    /// Clears all integers in this set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt = RoaringTreemap::new();
+    /// rt.insert(1);
+    /// assert_eq!(rt.contains(1), true);
+    /// rt.clear();
+    /// assert_eq!(rt.contains(1), false);
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns `true` if there are no integers in this set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt = RoaringTreemap::new();
+    /// assert_eq!(rt.is_empty(), true);
+    ///
+    /// rt.insert(3);
+    /// assert_eq!(rt.is_empty(), false);
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns the number of distinct integers added to the set. This is a `u128` so that it can
+    /// count every `u64` value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt = RoaringTreemap::new();
+    /// assert_eq!(rt.len(), 0);
+    ///
+    /// rt.insert(3);
+    /// assert_eq!(rt.len(), 1);
+    ///
+    /// rt.insert(3);
+    /// rt.insert(4);
+    /// assert_eq!(rt.len(), 2);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> u128 {
+        self.map.values().map(|bitmap| bitmap.len() as u128).sum()
+    }
+
+    /// Returns the smallest integer in the set, or `None` if the set is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt = RoaringTreemap::new();
+    /// assert_eq!(rt.min(), None);
+    ///
+    /// rt.insert(3);
+    /// rt.insert(1 << 40);
+    /// assert_eq!(rt.min(), Some(3));
+    /// ```
+    #[inline]
+    pub fn min(&self) -> Option<u64> {
+        self.map.iter().next().and_then(|(&key, bitmap)| bitmap.min().map(|min| Halveable::join(key, min)))
+    }
+
+    /// Returns the largest integer in the set, or `None` if the set is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt = RoaringTreemap::new();
+    /// assert_eq!(rt.max(), None);
+    ///
+    /// rt.insert(3);
+    /// rt.insert(1 << 40);
+    /// assert_eq!(rt.max(), Some(1 << 40));
+    /// ```
+    #[inline]
+    pub fn max(&self) -> Option<u64> {
+        self.map.iter().next_back().and_then(|(&key, bitmap)| bitmap.max().map(|max| Halveable::join(key, max)))
+    }
+
+    /// Iterator over each value stored in the RoaringTreemap, guarantees values are ordered by value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt = RoaringTreemap::new();
+    ///
+    /// rt.insert(1);
+    /// rt.insert(6);
+    /// rt.insert(1 << 40);
+    ///
+    /// let mut iter = rt.iter();
+    ///
+    /// assert_eq!(iter.next(), Some(1));
+    /// assert_eq!(iter.next(), Some(6));
+    /// assert_eq!(iter.next(), Some(1 << 40));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    pub fn iter<'a>(&'a self) -> TreemapIter<'a> {
+        TreemapIter { outer: self.map.iter(), inner: None }
+    }
+
+    /// Returns an iterator over the union of this treemap with the `other` treemap.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = vec![1, 1 << 40].into_iter().collect();
+    /// let rt2: RoaringTreemap = vec![1, 3].into_iter().collect();
+    ///
+    /// let mut iter = rt1.union(&rt2);
+    ///
+    /// assert_eq!(iter.next(), Some(1));
+    /// assert_eq!(iter.next(), Some(3));
+    /// assert_eq!(iter.next(), Some(1 << 40));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    pub fn union<'a>(&'a self, other: &'a Self) -> TreemapUnionIter<'a> {
+        TreemapUnionIter { inner: PairsIter::new(self, other, union_op) }
+    }
+
+    /// Returns an iterator over the intersection of this treemap with the `other` treemap.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = vec![1, 2, 1 << 40].into_iter().collect();
+    /// let rt2: RoaringTreemap = vec![1, 3, 1 << 40].into_iter().collect();
+    ///
+    /// let mut iter = rt1.intersection(&rt2);
+    ///
+    /// assert_eq!(iter.next(), Some(1));
+    /// assert_eq!(iter.next(), Some(1 << 40));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    pub fn intersection<'a>(&'a self, other: &'a Self) -> TreemapIntersectionIter<'a> {
+        TreemapIntersectionIter { inner: PairsIter::new(self, other, intersection_op) }
+    }
+
+    /// Returns an iterator over the set of values in `this` that are not in `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = vec![1, 2, 1 << 40].into_iter().collect();
+    /// let rt2: RoaringTreemap = vec![1, 3].into_iter().collect();
+    ///
+    /// let mut iter = rt1.difference(&rt2);
+    ///
+    /// assert_eq!(iter.next(), Some(2));
+    /// assert_eq!(iter.next(), Some(1 << 40));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    pub fn difference<'a>(&'a self, other: &'a Self) -> TreemapDifferenceIter<'a> {
+        TreemapDifferenceIter { inner: PairsIter::new(self, other, difference_op) }
+    }
+
+    /// Returns an iterator over the set of values in `this` that are not in `other` + in `other`
+    /// that are not in `this`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = vec![1, 2, 1 << 40].into_iter().collect();
+    /// let rt2: RoaringTreemap = vec![1, 3].into_iter().collect();
+    ///
+    /// let mut iter = rt1.symmetric_difference(&rt2);
+    ///
+    /// assert_eq!(iter.next(), Some(2));
+    /// assert_eq!(iter.next(), Some(3));
+    /// assert_eq!(iter.next(), Some(1 << 40));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    pub fn symmetric_difference<'a>(&'a self, other: &'a Self) -> TreemapSymmetricDifferenceIter<'a> {
+        TreemapSymmetricDifferenceIter { inner: PairsIter::new(self, other, symmetric_difference_op) }
+    }
+
+    /// Returns true if the set has no elements in common with other. This is equivalent to
+    /// checking for an empty intersection.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt1 = RoaringTreemap::new();
+    /// let mut rt2 = RoaringTreemap::new();
+    ///
+    /// rt1.insert(1);
+    ///
+    /// assert_eq!(rt1.is_disjoint(&rt2), true);
+    ///
+    /// rt2.insert(1);
+    ///
+    /// assert_eq!(rt1.is_disjoint(&rt2), false);
+    /// ```
+    #[inline]
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.map.iter().all(|(key, bitmap)| match other.map.get(key) {
+            Some(other) => bitmap.is_disjoint(other),
+            None => true,
+        })
+    }
+
+    /// Returns `true` if this set is a subset of `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt1 = RoaringTreemap::new();
+    /// let mut rt2 = RoaringTreemap::new();
+    ///
+    /// rt1.insert(1);
+    ///
+    /// assert_eq!(rt1.is_subset(&rt2), false);
+    ///
+    /// rt2.insert(1);
+    ///
+    /// assert_eq!(rt1.is_subset(&rt2), true);
+    ///
+    /// rt1.insert(2);
+    ///
+    /// assert_eq!(rt1.is_subset(&rt2), false);
+    /// ```
+    #[inline]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.map.iter().all(|(key, bitmap)| match other.map.get(key) {
+            Some(other) => bitmap.is_subset(other),
+            None => false,
+        })
+    }
+
+    /// Returns `true` if this set is a superset of `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt1 = RoaringTreemap::new();
+    /// let mut rt2 = RoaringTreemap::new();
+    ///
+    /// rt1.insert(1);
+    ///
+    /// assert_eq!(rt2.is_superset(&rt1), false);
+    ///
+    /// rt2.insert(1);
+    ///
+    /// assert_eq!(rt2.is_superset(&rt1), true);
+    ///
+    /// rt1.insert(2);
+    ///
+    /// assert_eq!(rt2.is_superset(&rt1), false);
+    /// ```
+    #[inline]
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Unions in-place with the specified other treemap.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (3..5u64).collect();
+    /// let rt3: RoaringTreemap = (1..5u64).collect();
+    ///
+    /// rt1.union_with(&rt2);
+    ///
+    /// assert_eq!(rt1, rt3);
+    /// ```
+    #[inline]
+    pub fn union_with(&mut self, other: &Self) {
+        for (&key, other) in &other.map {
+            match self.map.entry(key) {
+                Entry::Vacant(entry) => { entry.insert(other.clone()); },
+                Entry::Occupied(mut entry) => entry.get_mut().union_with(other),
+            }
+        }
+    }
+
+    /// Intersects in-place with the specified other treemap.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (3..5u64).collect();
+    /// let rt3: RoaringTreemap = (3..4u64).collect();
+    ///
+    /// rt1.intersect_with(&rt2);
+    ///
+    /// assert_eq!(rt1, rt3);
+    /// ```
+    #[inline]
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.map.retain(|key, bitmap| match other.map.get(key) {
+            Some(other) => {
+                bitmap.intersect_with(other);
+                !bitmap.is_empty()
+            },
+            None => false,
+        });
+    }
+
+    /// Removes all values in the specified other treemap from self, in-place.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (3..5u64).collect();
+    /// let rt3: RoaringTreemap = (1..3u64).collect();
+    ///
+    /// rt1.difference_with(&rt2);
+    ///
+    /// assert_eq!(rt1, rt3);
+    /// ```
+    #[inline]
+    pub fn difference_with(&mut self, other: &Self) {
+        self.map.retain(|key, bitmap| match other.map.get(key) {
+            Some(other) => {
+                bitmap.difference_with(other);
+                !bitmap.is_empty()
+            },
+            None => true,
+        });
+    }
+
+    /// Replaces this treemap with one that is equivalent to `self XOR other`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let mut rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (3..6u64).collect();
+    /// let rt3: RoaringTreemap = ((1..3u64).chain(4..6u64)).collect();
+    ///
+    /// rt1.symmetric_difference_with(&rt2);
+    ///
+    /// assert_eq!(rt1, rt3);
+    /// ```
+    #[inline]
+    pub fn symmetric_difference_with(&mut self, other: &Self) {
+        for (&key, other) in &other.map {
+            match self.map.entry(key) {
+                Entry::Vacant(entry) => { entry.insert(other.clone()); },
+                Entry::Occupied(mut entry) => {
+                    entry.get_mut().symmetric_difference_with(other);
+                    if entry.get().is_empty() {
+                        entry.remove();
+                    }
+                },
+            }
+        }
+    }
+
+    /// Converts every container to whichever representation takes the least space. Returns `true`
+    /// if any container changed representation.
+    #[inline]
+    pub fn run_optimize(&mut self) -> bool {
+        self.map.values_mut().map(|bitmap| bitmap.run_optimize()).fold(false, |changed, c| changed || c)
+    }
+
+    /// Converts every run container back to an array or bitmap container. Returns `true` if any
+    /// container changed representation.
+    #[inline]
+    pub fn remove_run_compression(&mut self) -> bool {
+        self.map.values_mut().map(|bitmap| bitmap.remove_run_compression()).fold(false, |changed, c| changed || c)
+    }
+
+    /// Serializes this treemap as the number of bitmaps followed by each key and the `to_raw64`
+    /// output of its bitmap.
+    #[inline]
+    pub fn to_raw64(&self) -> Vec<u64> {
+        let mut raw64 = vec![self.map.len() as u64];
+        for (&key, bitmap) in &self.map {
+            raw64.push(key as u64);
+            raw64.extend(bitmap.to_raw64());
+        }
+        raw64
+    }
+}
+
+impl RoaringTreemap {
+    /// Returns the number of bytes `serialize_into` will write for this treemap.
+    #[inline]
+    pub fn serialized_size(&self) -> usize {
+        8 + self.map.values().map(|bitmap| 4 + bitmap.serialized_size()).sum::<usize>()
+    }
+
+    /// Serializes this treemap into the portable 64-bit [Roaring format][format]: the number of
+    /// bitmaps, followed by each key and its bitmap in the 32-bit format.
+    ///
+    /// [format]: https://github.com/RoaringBitmap/RoaringFormatSpec#extension-for-64-bit-implementations
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).chain(1 << 40..(1 << 40) + 4).collect();
+    /// let mut bytes = vec![];
+    /// rt1.serialize_into(&mut bytes).unwrap();
+    /// let rt2 = RoaringTreemap::deserialize_from(&bytes[..]).unwrap();
+    ///
+    /// assert_eq!(rt1, rt2);
+    /// ```
+    pub fn serialize_into<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        serialization::write_u64(&mut writer, self.map.len() as u64)?;
+        for (&key, bitmap) in &self.map {
+            serialization::write_u32(&mut writer, key)?;
+            bitmap.serialize_into(&mut writer)?;
+        }
+        Ok(())
+    }
+
+    /// Deserializes a treemap from the portable 64-bit [Roaring format][format], checking that
+    /// the data describes a valid treemap.
+    ///
+    /// [format]: https://github.com/RoaringBitmap/RoaringFormatSpec#extension-for-64-bit-implementations
+    pub fn deserialize_from<R: io::Read>(mut reader: R) -> ::std::result::Result<Self, DeserializeError> {
+        let mut rt = RoaringTreemap::new();
+        let num_bitmaps = serialization::read_u64(&mut reader)?;
+        for _ in 0..num_bitmaps {
+            let key = serialization::read_u32(&mut reader)?;
+            if rt.map.keys().next_back().map_or(false, |&last| last >= key) {
+                return Err(DeserializeError::UnsortedKeys);
+            }
+            let bitmap = RoaringBitmap::deserialize_from(&mut reader)?;
+            if bitmap.is_empty() {
+                return Err(DeserializeError::EmptyContainer);
+            }
+            rt.map.insert(key, bitmap);
+        }
+        Ok(rt)
+    }
+}
+
+/// An iterator for `RoaringTreemap`.
+pub struct TreemapIter<'a> {
+    outer: btree_map::Iter<'a, u32, RoaringBitmap<u32>>,
+    inner: Option<(u32, Iter<'a, u32>)>,
+}
+
+impl<'a> Iterator for TreemapIter<'a> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            if let Some((key, ref mut iter)) = self.inner {
+                if let Some(value) = iter.next() {
+                    return Some(Halveable::join(key, value));
+                }
+            }
+            match self.outer.next() {
+                Some((&key, bitmap)) => self.inner = Some((key, bitmap.iter())),
+                None => return None,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let next = self.outer.clone().map(|(_, bitmap)| bitmap.len() as usize).sum::<usize>();
+        match self.inner {
+            Some((_, ref iter)) => match iter.size_hint() {
+                (min, max) => (next + min, max.map(|m| next + m)),
+            },
+            None => (next, Some(next)),
+        }
+    }
+}
+
+/// An owning iterator for `RoaringTreemap`.
+pub struct TreemapIntoIter {
+    outer: btree_map::IntoIter<u32, RoaringBitmap<u32>>,
+    inner: Option<(u32, IntoIter<u32>)>,
+    remaining: u128,
+}
+
+impl Iterator for TreemapIntoIter {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            if let Some((key, ref mut iter)) = self.inner {
+                if let Some(value) = iter.next() {
+                    self.remaining -= 1;
+                    return Some(Halveable::join(key, value));
+                }
+            }
+            match self.outer.next() {
+                Some((key, bitmap)) => self.inner = Some((key, bitmap.into_iter())),
+                None => return None,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remaining <= usize::MAX as u128 {
+            (self.remaining as usize, Some(self.remaining as usize))
+        } else {
+            (usize::MAX, None)
+        }
+    }
+}
+
+impl IntoIterator for RoaringTreemap {
+    type Item = u64;
+    type IntoIter = TreemapIntoIter;
+    #[inline]
+    fn into_iter(self) -> TreemapIntoIter {
+        let remaining = self.len();
+        TreemapIntoIter { outer: self.map.into_iter(), inner: None, remaining: remaining }
+    }
+}
+
+// The values of one bitmap pair, either from a bitmap with no counterpart or computed lazily
+// from both bitmaps.
+enum PairValues<'a, I> {
+    One(Iter<'a, u32>),
+    Both(I),
+}
+
+impl<'a, I: Iterator<Item = u32>> Iterator for PairValues<'a, I> {
+    type Item = u32;
+
+    #[inline]
+    fn next(&mut self) -> Option<u32> {
+        match *self {
+            PairValues::One(ref mut iter) => iter.next(),
+            PairValues::Both(ref mut iter) => iter.next(),
+        }
+    }
+}
+
+type PairOp<'a, I> = fn(Option<&'a RoaringBitmap<u32>>, Option<&'a RoaringBitmap<u32>>) -> Option<PairValues<'a, I>>;
+
+// Walks the bitmaps of two treemaps in key order, yielding the values `op` produces for each
+// pair of bitmaps sharing a key.
+struct PairsIter<'a, I> {
+    iter1: Peekable<btree_map::Iter<'a, u32, RoaringBitmap<u32>>>,
+    iter2: Peekable<btree_map::Iter<'a, u32, RoaringBitmap<u32>>>,
+    current: Option<(u32, PairValues<'a, I>)>,
+    op: PairOp<'a, I>,
+}
+
+impl<'a, I: Iterator<Item = u32>> PairsIter<'a, I> {
+    #[inline]
+    fn new(this: &'a RoaringTreemap, other: &'a RoaringTreemap, op: PairOp<'a, I>) -> PairsIter<'a, I> {
+        PairsIter { iter1: this.map.iter().peekable(), iter2: other.map.iter().peekable(), current: None, op: op }
+    }
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            if let Some((key, ref mut values)) = self.current {
+                if let Some(value) = values.next() {
+                    return Some(Halveable::join(key, value));
+                }
+            }
+            let (key1, key2) = (self.iter1.peek().map(|&(&key, _)| key), self.iter2.peek().map(|&(&key, _)| key));
+            let (key, b1, b2) = match (key1, key2) {
+                (None, None) => {
+                    self.current = None;
+                    return None;
+                },
+                (Some(key1), Some(key2)) if key1 == key2 => (key1, self.iter1.next(), self.iter2.next()),
+                (Some(key1), Some(key2)) if key1 < key2 => (key1, self.iter1.next(), None),
+                (Some(key1), None) => (key1, self.iter1.next(), None),
+                (_, Some(key2)) => (key2, None, self.iter2.next()),
+            };
+            self.current = (self.op)(b1.map(|(_, b)| b), b2.map(|(_, b)| b)).map(|values| (key, values));
+        }
+    }
+}
+
+fn union_op<'a>(b1: Option<&'a RoaringBitmap<u32>>, b2: Option<&'a RoaringBitmap<u32>>) -> Option<PairValues<'a, UnionIter<'a, u32>>> {
+    match (b1, b2) {
+        (Some(b1), Some(b2)) => Some(PairValues::Both(b1.union(b2))),
+        (Some(b), None) | (None, Some(b)) => Some(PairValues::One(b.iter())),
+        (None, None) => None,
+    }
+}
+
+fn intersection_op<'a>(b1: Option<&'a RoaringBitmap<u32>>, b2: Option<&'a RoaringBitmap<u32>>) -> Option<PairValues<'a, IntersectionIter<'a, u32>>> {
+    match (b1, b2) {
+        (Some(b1), Some(b2)) => Some(PairValues::Both(b1.intersection(b2))),
+        _ => None,
+    }
+}
+
+fn difference_op<'a>(b1: Option<&'a RoaringBitmap<u32>>, b2: Option<&'a RoaringBitmap<u32>>) -> Option<PairValues<'a, DifferenceIter<'a, u32>>> {
+    match (b1, b2) {
+        (Some(b1), Some(b2)) => Some(PairValues::Both(b1.difference(b2))),
+        (Some(b1), None) => Some(PairValues::One(b1.iter())),
+        _ => None,
+    }
+}
+
+fn symmetric_difference_op<'a>(b1: Option<&'a RoaringBitmap<u32>>, b2: Option<&'a RoaringBitmap<u32>>) -> Option<PairValues<'a, SymmetricDifferenceIter<'a, u32>>> {
+    match (b1, b2) {
+        (Some(b1), Some(b2)) => Some(PairValues::Both(b1.symmetric_difference(b2))),
+        (Some(b), None) | (None, Some(b)) => Some(PairValues::One(b.iter())),
+        (None, None) => None,
+    }
+}
+
+/// An iterator over the union of two `RoaringTreemap`s.
+pub struct TreemapUnionIter<'a> {
+    inner: PairsIter<'a, UnionIter<'a, u32>>,
+}
+
+impl<'a> Iterator for TreemapUnionIter<'a> {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<u64> {
+        self.inner.next()
+    }
+}
+
+/// An iterator over the intersection of two `RoaringTreemap`s.
+pub struct TreemapIntersectionIter<'a> {
+    inner: PairsIter<'a, IntersectionIter<'a, u32>>,
+}
+
+impl<'a> Iterator for TreemapIntersectionIter<'a> {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<u64> {
+        self.inner.next()
+    }
+}
+
+/// An iterator over the difference of two `RoaringTreemap`s.
+pub struct TreemapDifferenceIter<'a> {
+    inner: PairsIter<'a, DifferenceIter<'a, u32>>,
+}
+
+impl<'a> Iterator for TreemapDifferenceIter<'a> {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<u64> {
+        self.inner.next()
+    }
+}
+
+/// An iterator over the symmetric difference of two `RoaringTreemap`s.
+pub struct TreemapSymmetricDifferenceIter<'a> {
+    inner: PairsIter<'a, SymmetricDifferenceIter<'a, u32>>,
+}
+
+impl<'a> Iterator for TreemapSymmetricDifferenceIter<'a> {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<u64> {
+        self.inner.next()
+    }
+}
+
+impl FromIterator<u64> for RoaringTreemap {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = u64>>(iterator: I) -> Self {
+        let mut rt = RoaringTreemap::new();
+        rt.extend(iterator);
+        rt
+    }
+}
+
+impl<'a> FromIterator<&'a u64> for RoaringTreemap {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = &'a u64>>(iterator: I) -> Self {
+        let mut rt = RoaringTreemap::new();
+        rt.extend(iterator);
+        rt
+    }
+}
+
+impl Extend<u64> for RoaringTreemap {
+    #[inline]
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, iterator: I) {
+        for value in iterator {
+            self.insert(value);
+        }
+    }
+}
+
+impl<'a> Extend<&'a u64> for RoaringTreemap {
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a u64>>(&mut self, iterator: I) {
+        for value in iterator {
+            self.insert(*value);
+        }
+    }
+}
+
+impl BitOr<RoaringTreemap> for RoaringTreemap {
+    type Output = Self;
+
+    /// Unions the `rhs` into this `RoaringTreemap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (2..5u64).collect();
+    /// let rt3: RoaringTreemap = (1..5u64).collect();
+    ///
+    /// let rt4 = rt1 | rt2;
+    ///
+    /// assert_eq!(rt3, rt4);
+    /// ```
+    #[inline]
+    fn bitor(mut self, rhs: Self) -> Self {
+        self.union_with(&rhs);
+        self
+    }
+}
+
+impl<'a> BitOr<RoaringTreemap> for &'a RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    /// Unions`rhs` and `self`, writes result in place to `rhs`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (2..5u64).collect();
+    /// let rt3: RoaringTreemap = (1..5u64).collect();
+    ///
+    /// let rt4 = &rt1 | rt2;
+    ///
+    /// assert_eq!(rt3, rt4);
+    /// ```
+    #[inline]
+    fn bitor(self, mut rhs: RoaringTreemap) -> RoaringTreemap {
+        rhs.union_with(self);
+        rhs
+    }
+}
+
+impl<'a, 'b> BitOr<&'a RoaringTreemap> for &'b RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    /// Unions`rhs` and `self`, allocates new treemap for result.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (2..5u64).collect();
+    /// let rt3: RoaringTreemap = (1..5u64).collect();
+    ///
+    /// let rt4 = rt1 | &rt2;
+    ///
+    /// assert_eq!(rt3, rt4);
+    /// ```
+    #[inline]
+    fn bitor(self, rhs: &'a RoaringTreemap) -> RoaringTreemap {
+        let mut result = self.clone();
+        result.union_with(rhs);
+        result
+    }
+}
+
+impl<'a> BitOr<&'a RoaringTreemap> for RoaringTreemap {
+    type Output = Self;
+
+    /// Unions the `rhs` into this `RoaringTreemap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (2..5u64).collect();
+    /// let rt3: RoaringTreemap = (1..5u64).collect();
+    ///
+    /// let rt4 = rt1 | &rt2;
+    ///
+    /// assert_eq!(rt3, rt4);
+    /// ```
+    #[inline]
+    fn bitor(mut self, rhs: &'a Self) -> Self {
+        self.union_with(rhs);
+        self
+    }
+}
+
+impl BitAnd<RoaringTreemap> for RoaringTreemap {
+    type Output = Self;
+
+    /// Intersects the `rhs` into this `RoaringTreemap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (2..5u64).collect();
+    /// let rt3: RoaringTreemap = (2..4u64).collect();
+    ///
+    /// let rt4 = rt1 & rt2;
+    ///
+    /// assert_eq!(rt3, rt4);
+    /// ```
+    #[inline]
+    fn bitand(mut self, rhs: Self) -> Self {
+        self.intersect_with(&rhs);
+        self
+    }
+}
+
+impl<'a> BitAnd<&'a RoaringTreemap> for RoaringTreemap {
+    type Output = Self;
+
+    /// Intersects the `rhs` into this `RoaringTreemap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (2..5u64).collect();
+    /// let rt3: RoaringTreemap = (2..4u64).collect();
+    ///
+    /// let rt4 = rt1 & &rt2;
+    ///
+    /// assert_eq!(rt3, rt4);
+    /// ```
+    #[inline]
+    fn bitand(mut self, rhs: &'a Self) -> Self {
+        self.intersect_with(rhs);
+        self
+    }
+}
+
+impl<'a> BitAnd<RoaringTreemap> for &'a RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    /// Intersects `self` into the `rhs` `RoaringTreemap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (2..5u64).collect();
+    /// let rt3: RoaringTreemap = (2..4u64).collect();
+    ///
+    /// let rt4 = &rt1 & rt2;
+    ///
+    /// assert_eq!(rt3, rt4);
+    /// ```
+    #[inline]
+    fn bitand(self, mut rhs: RoaringTreemap) -> RoaringTreemap {
+        rhs.intersect_with(self);
+        rhs
+    }
+}
+
+impl<'a, 'b> BitAnd<&'a RoaringTreemap> for &'b RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    /// Intersects `self` and `rhs` into a new `RoaringTreemap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (2..5u64).collect();
+    /// let rt3: RoaringTreemap = (2..4u64).collect();
+    ///
+    /// let rt4 = &rt1 & &rt2;
+    ///
+    /// assert_eq!(rt3, rt4);
+    /// ```
+    #[inline]
+    fn bitand(self, rhs: &'a RoaringTreemap) -> RoaringTreemap {
+        let mut result = self.clone();
+        result.intersect_with(rhs);
+        result
+    }
+}
+
+impl Sub<RoaringTreemap> for RoaringTreemap {
+    type Output = Self;
+
+    /// Subtracts the `rhs` into this `RoaringTreemap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (3..5u64).collect();
+    /// let rt3: RoaringTreemap = (1..3u64).collect();
+    ///
+    /// let rt4 = rt1 - rt2;
+    ///
+    /// assert_eq!(rt3, rt4);
+    /// ```
+    #[inline]
+    fn sub(mut self, rhs: Self) -> Self {
+        self.difference_with(&rhs);
+        self
+    }
+}
+
+impl<'a> Sub<&'a RoaringTreemap> for RoaringTreemap {
+    type Output = Self;
+
+    /// Subtracts the `rhs` into this `RoaringTreemap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (3..5u64).collect();
+    /// let rt3: RoaringTreemap = (1..3u64).collect();
+    ///
+    /// let rt4 = rt1 - &rt2;
+    ///
+    /// assert_eq!(rt3, rt4);
+    /// ```
+    #[inline]
+    fn sub(mut self, rhs: &'a Self) -> Self {
+        self.difference_with(rhs);
+        self
+    }
+}
+
+impl<'a, 'b> Sub<&'a RoaringTreemap> for &'b RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    /// Subtracts `rhs` from `self` and allocates a new `RoaringTreemap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (3..5u64).collect();
+    /// let rt3: RoaringTreemap = (1..3u64).collect();
+    ///
+    /// let rt4 = &rt1 - &rt2;
+    ///
+    /// assert_eq!(rt3, rt4);
+    /// ```
+    #[inline]
+    fn sub(self, rhs: &'a RoaringTreemap) -> RoaringTreemap {
+        let mut result = self.clone();
+        result.difference_with(rhs);
+        result
+    }
+}
+
+impl BitXor<RoaringTreemap> for RoaringTreemap {
+    type Output = Self;
+
+    /// Exclusive ors the `rhs` into this `RoaringTreemap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (3..6u64).collect();
+    /// let rt3: RoaringTreemap = ((1..3u64).chain(4..6u64)).collect();
+    ///
+    /// let rt4 = rt1 ^ rt2;
+    ///
+    /// assert_eq!(rt3, rt4);
+    /// ```
+    #[inline]
+    fn bitxor(mut self, rhs: Self) -> Self {
+        self.symmetric_difference_with(&rhs);
+        self
+    }
+}
+
+impl<'a> BitXor<&'a RoaringTreemap> for RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    /// Exclusive ors the `rhs` into this `RoaringTreemap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (3..6u64).collect();
+    /// let rt3: RoaringTreemap = ((1..3u64).chain(4..6u64)).collect();
+    ///
+    /// let rt4 = rt1 ^ &rt2;
+    ///
+    /// assert_eq!(rt3, rt4);
+    /// ```
+    #[inline]
+    fn bitxor(mut self, rhs: &'a Self) -> Self {
+        self.symmetric_difference_with(rhs);
+        self
+    }
+}
+
+impl<'a> BitXor<RoaringTreemap> for &'a RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    /// Exclusive ors `rhs` and `self`, writes result in place to `rhs`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (3..6u64).collect();
+    /// let rt3: RoaringTreemap = ((1..3u64).chain(4..6u64)).collect();
+    ///
+    /// let rt4 = &rt1 ^ rt2;
+    ///
+    /// assert_eq!(rt3, rt4);
+    /// ```
+    #[inline]
+    fn bitxor(self, mut rhs: RoaringTreemap) -> RoaringTreemap {
+        rhs.symmetric_difference_with(self);
+        rhs
+    }
+}
+
+impl<'a, 'b> BitXor<&'a RoaringTreemap> for &'b RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    /// Exclusive ors `rhs` and `self`, allocates a new treemap for the result.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use roaring::RoaringTreemap;
+    ///
+    /// let rt1: RoaringTreemap = (1..4u64).collect();
+    /// let rt2: RoaringTreemap = (3..6u64).collect();
+    /// let rt3: RoaringTreemap = ((1..3u64).chain(4..6u64)).collect();
+    ///
+    /// let rt4 = &rt1 ^ &rt2;
+    ///
+    /// assert_eq!(rt3, rt4);
+    /// ```
+    #[inline]
+    fn bitxor(self, rhs: &'a RoaringTreemap) -> RoaringTreemap {
+        let mut result = self.clone();
+        result.symmetric_difference_with(rhs);
+        result
+    }
+}
+
+impl Debug for RoaringTreemap {
+    #[inline]
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        if self.len() < 16 {
+            format!("RoaringTreemap<{:?}>", self.iter().collect::<Vec<u64>>()).fmt(formatter)
+        } else {
+            format!("RoaringTreemap<{:?} values between {:?} and {:?}>", self.len(), self.min().unwrap(), self.max().unwrap()).fmt(formatter)
+        }
+    }
+}
@@ -2,10 +2,9 @@
 
 use std::fmt::Debug;
 use std::num::ParseIntError;
+use std::ops::{ Bound, RangeBounds };
 
-use num::traits::{ PrimInt, Num };
-
-pub enum Either<Left, Right> { Left(Left), Right(Right) }
+use num::traits::{ PrimInt, Num, One, Zero, Bounded };
 
 pub trait Halveable {
     type HalfSize: ExtInt;
@@ -88,6 +87,23 @@ pub fn cast<T: To64, U: From>(n: T) -> U {
     From::from(n)
 }
 
+/// Converts `range` to its inclusive `(start, end)` bounds, or `None` if it is empty.
+pub fn convert_range<Size: ExtInt, R: RangeBounds<Size>>(range: R) -> Option<(Size, Size)> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) if start == Bounded::max_value() => return None,
+        Bound::Excluded(&start) => start + One::one(),
+        Bound::Unbounded => Zero::zero(),
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end,
+        Bound::Excluded(&end) if end == Zero::zero() => return None,
+        Bound::Excluded(&end) => end - One::one(),
+        Bound::Unbounded => Bounded::max_value(),
+    };
+    if start <= end { Some((start, end)) } else { None }
+}
+
 #[cfg(test)]
 mod test {
     use super::{ Halveable };
@@ -0,0 +1,102 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+#[test]
+fn advance_to_within_array() {
+    let bitmap: RoaringBitmap<u32> = (0..1000u32).map(|i| i * 3).collect();
+    let mut iter = bitmap.iter();
+    iter.advance_to(1000);
+    assert_eq!(iter.len(), 666);
+    assert_eq!(iter.next(), Some(1002));
+    iter.advance_to(2997);
+    assert_eq!(iter.collect::<Vec<u32>>(), vec![2997]);
+}
+
+#[test]
+fn advance_to_within_bitmap() {
+    let bitmap: RoaringBitmap<u32> = (0..60000u32).filter(|i| i % 5 != 0).collect();
+    let mut iter = bitmap.iter();
+    iter.advance_to(50000);
+    assert_eq!(iter.len(), 8000);
+    assert_eq!(iter.next(), Some(50001));
+    iter.advance_to(59999);
+    assert_eq!(iter.collect::<Vec<u32>>(), vec![59999]);
+}
+
+#[test]
+fn advance_to_run_gap() {
+    let mut bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    bitmap.insert_range(0..10);
+    bitmap.insert_range(1000..3000);
+    bitmap.run_optimize();
+    let mut iter = bitmap.iter();
+    iter.advance_to(500);
+    assert_eq!(iter.len(), 2000);
+    assert_eq!(iter.next(), Some(1000));
+    iter.advance_to(2500);
+    assert_eq!(iter.next(), Some(2500));
+}
+
+#[test]
+fn advance_to_next_container() {
+    let bitmap: RoaringBitmap<u32> = vec![1u32, 5, 65535, 100000, 100010].into_iter().collect();
+    let mut iter = bitmap.iter();
+    iter.advance_to(65536);
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.collect::<Vec<u32>>(), vec![100000, 100010]);
+
+    let mut iter = bitmap.iter();
+    iter.advance_to(400000);
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn advance_to_backwards() {
+    let bitmap: RoaringBitmap<u32> = vec![1, 5, 100000, 100010].into_iter().collect();
+    let mut iter = bitmap.iter();
+    iter.advance_to(100005);
+    iter.advance_to(0);
+    assert_eq!(iter.collect::<Vec<u32>>(), vec![100010]);
+}
+
+#[test]
+fn advance_to_after_next_back() {
+    let bitmap: RoaringBitmap<u32> = vec![1u32, 5, 100000, 100010, 200000].into_iter().collect();
+    let mut iter = bitmap.iter();
+    assert_eq!(iter.next_back(), Some(200000));
+    assert_eq!(iter.next_back(), Some(100010));
+    iter.advance_to(100005);
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+
+    let mut iter = bitmap.iter();
+    assert_eq!(iter.next_back(), Some(200000));
+    iter.advance_to(6);
+    assert_eq!(iter.collect::<Vec<u32>>(), vec![100000, 100010]);
+}
+
+#[test]
+fn set_ops() {
+    let bitmap1: RoaringBitmap<u32> = vec![1u32, 5, 1500, 70000, 70002].into_iter().collect();
+    let mut bitmap2: RoaringBitmap<u32> = RoaringBitmap::new();
+    bitmap2.insert_range(1000..2000);
+    bitmap2.insert(70002);
+    bitmap2.run_optimize();
+
+    let mut union = bitmap1.union(&bitmap2);
+    union.advance_to(1998);
+    assert_eq!(union.collect::<Vec<u32>>(), vec![1998, 1999, 70000, 70002]);
+
+    let mut intersection = bitmap1.intersection(&bitmap2);
+    intersection.advance_to(1501);
+    assert_eq!(intersection.collect::<Vec<u32>>(), vec![70002]);
+
+    let mut difference = bitmap1.difference(&bitmap2);
+    difference.advance_to(2);
+    assert_eq!(difference.collect::<Vec<u32>>(), vec![5, 70000]);
+
+    let mut symmetric_difference = bitmap1.symmetric_difference(&bitmap2);
+    symmetric_difference.advance_to(1499);
+    assert_eq!(symmetric_difference.collect::<Vec<u32>>(), (1499..2000u32).filter(|&i| i != 1500).chain(vec![70000]).collect::<Vec<u32>>());
+}
@@ -0,0 +1,67 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+fn bitmaps() -> Vec<RoaringBitmap<u32>> {
+    let mut run: RoaringBitmap<u32> = RoaringBitmap::new();
+    run.insert_range(0..70000);
+    run.run_optimize();
+    vec![
+        (0..3000u32).map(|i| i * 3).collect(),
+        (0..60000u32).filter(|i| i % 5 != 0).collect(),
+        (0..3000u32).map(|i| i * 7).chain(200000..200010).collect(),
+        (0..300000u32).filter(|i| i % 2 == 0).collect(),
+        run,
+    ]
+}
+
+#[test]
+fn union_all() {
+    let bitmaps = bitmaps();
+    let mut expected = RoaringBitmap::new();
+    for bitmap in &bitmaps {
+        expected.union_with(bitmap);
+    }
+    assert_eq!(RoaringBitmap::union_all(&bitmaps), expected);
+    assert_eq!(RoaringBitmap::union_all(&bitmaps[..3]), &(&bitmaps[0] | &bitmaps[1]) | &bitmaps[2]);
+}
+
+#[test]
+fn union_all_small() {
+    let bitmaps: Vec<RoaringBitmap<u32>> = (0..10u32).map(|i| (i * 10..i * 10 + 15).collect()).collect();
+    assert_eq!(RoaringBitmap::union_all(&bitmaps), (0..105u32).collect());
+}
+
+#[test]
+fn union_all_full() {
+    let bitmap1: RoaringBitmap<u32> = (0..40000u32).collect();
+    let bitmap2: RoaringBitmap<u32> = (30000..65536u32).collect();
+    assert_eq!(RoaringBitmap::union_all(vec![&bitmap1, &bitmap2]), (0..65536u32).collect());
+}
+
+#[test]
+fn intersection_all() {
+    let bitmaps = bitmaps();
+    let mut expected = bitmaps[0].clone();
+    for bitmap in &bitmaps[1..] {
+        expected.intersect_with(bitmap);
+    }
+    assert_eq!(RoaringBitmap::intersection_all(&bitmaps), expected);
+    assert_eq!(RoaringBitmap::intersection_all(&bitmaps[1..]), &(&bitmaps[1] & &bitmaps[2]) & &(&bitmaps[3] & &bitmaps[4]));
+}
+
+#[test]
+fn intersection_all_disjoint_keys() {
+    let bitmap1: RoaringBitmap<u32> = (0..100u32).collect();
+    let bitmap2: RoaringBitmap<u32> = (100000..100100u32).collect();
+    assert!(RoaringBitmap::intersection_all(vec![&bitmap1, &bitmap2, &bitmap1]).is_empty());
+}
+
+#[test]
+fn empty() {
+    let none: Vec<RoaringBitmap<u32>> = Vec::new();
+    assert!(RoaringBitmap::union_all(&none).is_empty());
+    assert!(RoaringBitmap::intersection_all(&none).is_empty());
+    let bitmap: RoaringBitmap<u32> = (0..100u32).collect();
+    assert_eq!(RoaringBitmap::union_all(vec![&bitmap]), bitmap);
+    assert_eq!(RoaringBitmap::intersection_all(vec![&bitmap]), bitmap);
+}
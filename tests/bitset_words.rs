@@ -0,0 +1,95 @@
+extern crate roaring;
+use roaring::{ RoaringBitmap, NonSortedIntegers, OutOfRange };
+
+fn values() -> Vec<u32> {
+    (0..2000u32).map(|i| i * 3)
+        .chain(70000..140000)
+        .chain((200000..400000).filter(|i| i % 7 != 0))
+        .collect()
+}
+
+#[test]
+fn from_sorted_slice_empty() {
+    let bitmap = RoaringBitmap::<u32>::from_sorted_slice(&[]).unwrap();
+    assert_eq!(bitmap.is_empty(), true);
+}
+
+#[test]
+fn from_sorted_slice_matches_collect() {
+    let values = values();
+    let bitmap = RoaringBitmap::from_sorted_slice(&values).unwrap();
+    assert_eq!(bitmap, values.iter().collect());
+    assert_eq!(bitmap.len(), values.len() as u64);
+}
+
+#[test]
+fn from_sorted_slice_full_container() {
+    let values = (0..=65535u16).collect::<Vec<u16>>();
+    let bitmap = RoaringBitmap::from_sorted_slice(&values).unwrap();
+    assert_eq!(bitmap.len(), 65536);
+    assert_eq!(bitmap, values.iter().collect());
+}
+
+#[test]
+fn from_sorted_slice_unsorted() {
+    assert_eq!(RoaringBitmap::from_sorted_slice(&[1u32, 70000, 3]), Err(NonSortedIntegers { valid_until: 2 }));
+}
+
+#[test]
+fn from_bitset_words_empty() {
+    assert_eq!(RoaringBitmap::<u32>::from_bitset_words(&[]).unwrap().is_empty(), true);
+    assert_eq!(RoaringBitmap::<u32>::from_bitset_words(&[0; 5000]).unwrap().is_empty(), true);
+}
+
+#[test]
+fn from_bitset_words_matches_collect() {
+    let values = values();
+    let mut words = vec![0u64; 400000 / 64 + 1];
+    for &value in &values {
+        words[value as usize / 64] |= 1 << (value % 64);
+    }
+    let bitmap = RoaringBitmap::from_bitset_words(&words).unwrap();
+    assert_eq!(bitmap, values.iter().collect());
+    assert_eq!(bitmap.len(), values.len() as u64);
+}
+
+#[test]
+fn from_bitset_words_full_range() {
+    let bitmap: RoaringBitmap<u16> = RoaringBitmap::from_bitset_words(&[!0; 1024]).unwrap();
+    assert_eq!(bitmap.len(), 65536);
+}
+
+#[test]
+fn from_bitset_words_out_of_range() {
+    assert_eq!(RoaringBitmap::<u16>::from_bitset_words(&[0; 1025]).map(|bitmap| bitmap.len()), Ok(0));
+    let mut words = vec![!0; 1024];
+    words.extend_from_slice(&[0, 0, 1 << 5]);
+    assert_eq!(RoaringBitmap::<u16>::from_bitset_words(&words), Err(OutOfRange { value: 1026 * 64 + 5 }));
+}
+
+#[test]
+fn to_bitset_words_empty() {
+    assert_eq!(RoaringBitmap::<u32>::new().to_bitset_words(), Vec::<u64>::new());
+}
+
+#[test]
+fn to_bitset_words_round_trip() {
+    let mut bitmap = RoaringBitmap::from_sorted_slice(&values()).unwrap();
+    bitmap.insert_range(500000..600000);
+    bitmap.run_optimize();
+    let words = bitmap.to_bitset_words();
+    assert_eq!(words.len(), 599999 / 64 + 1);
+    assert_eq!(words.iter().map(|word| word.count_ones() as u64).sum::<u64>(), bitmap.len());
+    assert_eq!(RoaringBitmap::from_bitset_words(&words), Ok(bitmap));
+}
+
+#[test]
+fn to_bitset_words_u16() {
+    let bitmap: RoaringBitmap<u16> = vec![0u16, 255, 256, 65535].into_iter().collect();
+    let words = bitmap.to_bitset_words();
+    assert_eq!(words.len(), 1024);
+    assert_eq!(words[0], 1);
+    assert_eq!(words[3], 1 << 63);
+    assert_eq!(words[4], 1);
+    assert_eq!(words[1023], 1 << 63);
+}
@@ -0,0 +1,104 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+// A bitmap with array, bitmap and run containers, and gaps between them.
+fn bitmap() -> RoaringBitmap<u32> {
+    let mut bitmap: RoaringBitmap<u32> = (0..2000u32).map(|i| i * 7).collect();
+    bitmap.extend((131072..196608u32).filter(|i| i % 3 != 0));
+    bitmap.insert_range(300000..320000);
+    bitmap.run_optimize();
+    bitmap
+}
+
+fn flipped(bitmap: &RoaringBitmap<u32>, start: u32, end: u32) -> Vec<u32> {
+    let mut values = bitmap.iter().filter(|&value| value < start || value >= end).collect::<Vec<u32>>();
+    values.extend((start..end).filter(|&value| !bitmap.contains(value)));
+    values.sort();
+    values
+}
+
+#[test]
+fn flip_empty_range() {
+    let mut bitmap = bitmap();
+    bitmap.flip(100..100);
+    assert_eq!(bitmap, self::bitmap());
+}
+
+#[test]
+fn flip_within_containers() {
+    for &(start, end) in &[(0, 5000), (10, 14000), (131073, 196000), (305000, 310000), (250000, 260000)] {
+        let mut bitmap = bitmap();
+        let expected = flipped(&bitmap, start, end);
+        bitmap.flip(start..end);
+        assert_eq!(bitmap.iter().collect::<Vec<u32>>(), expected);
+        assert_eq!(bitmap.len(), expected.len() as u64);
+    }
+}
+
+#[test]
+fn flip_spanning_containers() {
+    let mut bitmap = bitmap();
+    let expected = flipped(&bitmap, 5, 400000);
+    bitmap.flip(5..400000);
+    assert_eq!(bitmap.iter().collect::<Vec<u32>>(), expected);
+    assert_eq!(bitmap.len(), expected.len() as u64);
+}
+
+#[test]
+fn flip_twice() {
+    let mut bitmap = bitmap();
+    bitmap.flip(1000..=350000);
+    bitmap.flip(1000..=350000);
+    assert_eq!(bitmap, self::bitmap());
+}
+
+#[test]
+fn flip_full_container() {
+    let mut bitmap: RoaringBitmap<u32> = (65536..131072u32).collect();
+    bitmap.flip(65536..131072);
+    assert_eq!(bitmap.is_empty(), true);
+    bitmap.flip(65536..131072);
+    assert_eq!(bitmap.len(), 65536);
+}
+
+#[test]
+fn flip_array_to_bitmap() {
+    let mut bitmap: RoaringBitmap<u32> = (0..100u32).map(|i| i * 5).collect();
+    let expected = flipped(&bitmap, 0, 10000);
+    bitmap.flip(0..10000);
+    assert_eq!(bitmap.iter().collect::<Vec<u32>>(), expected);
+    assert_eq!(bitmap.len(), 10000 - 100);
+}
+
+#[test]
+fn flip_full_range() {
+    let mut bitmap: RoaringBitmap<u16> = (0..1000u16).collect();
+    bitmap.flip(..);
+    assert_eq!(bitmap.len(), 65536 - 1000);
+    assert_eq!(bitmap.min(), Some(1000));
+    assert_eq!(bitmap.max(), Some(65535));
+}
+
+#[test]
+fn complement_in() {
+    let bitmap = bitmap();
+    for &(start, end) in &[(0, 5000), (10, 400000), (131073, 196000), (250000, 260000), (400000, 500000)] {
+        let complement = bitmap.complement_in(start..end);
+        let expected = (start..end).filter(|&value| !bitmap.contains(value)).collect::<Vec<u32>>();
+        assert_eq!(complement.iter().collect::<Vec<u32>>(), expected);
+        assert_eq!(complement.len(), expected.len() as u64);
+    }
+}
+
+#[test]
+fn complement_in_empty_range() {
+    assert_eq!(bitmap().complement_in(10..10).is_empty(), true);
+}
+
+#[test]
+fn complement_in_is_disjoint() {
+    let bitmap = bitmap();
+    let complement = bitmap.complement_in(..500000);
+    assert_eq!(complement.is_disjoint(&bitmap), true);
+    assert_eq!((complement | bitmap), (0..500000u32).collect());
+}
@@ -0,0 +1,78 @@
+extern crate roaring;
+use roaring::{ RoaringBitmap, NonSortedIntegers };
+
+#[test]
+fn from_sorted_iter_empty() {
+    let bitmap = RoaringBitmap::<u32>::from_sorted_iter(Vec::new()).unwrap();
+    assert_eq!(bitmap.is_empty(), true);
+}
+
+#[test]
+fn from_sorted_iter_matches_collect() {
+    let values = (0..2000u32).map(|i| i * 3)
+        .chain(70000..140000)
+        .chain((200000..400000).filter(|i| i % 7 != 0))
+        .collect::<Vec<u32>>();
+    let bitmap = RoaringBitmap::from_sorted_iter(values.iter().cloned()).unwrap();
+    assert_eq!(bitmap, values.iter().cloned().collect());
+    assert_eq!(bitmap.len(), values.len() as u64);
+    assert_eq!(bitmap.iter().collect::<Vec<u32>>(), values);
+}
+
+#[test]
+fn from_sorted_iter_full_container() {
+    let bitmap = RoaringBitmap::<u16>::from_sorted_iter(0..=65535).unwrap();
+    assert_eq!(bitmap.len(), 65536);
+    assert_eq!(bitmap.contains(65535), true);
+}
+
+#[test]
+fn from_sorted_iter_unsorted() {
+    let result = RoaringBitmap::<u32>::from_sorted_iter(vec![1, 2, 100000, 99999]);
+    assert_eq!(result, Err(NonSortedIntegers { valid_until: 3 }));
+}
+
+#[test]
+fn from_sorted_iter_duplicate() {
+    let result = RoaringBitmap::<u32>::from_sorted_iter(vec![1, 2, 2, 3]);
+    assert_eq!(result, Err(NonSortedIntegers { valid_until: 2 }));
+}
+
+#[test]
+fn extend_sorted_after_max() {
+    let mut bitmap: RoaringBitmap<u32> = (0..100u32).collect();
+    assert_eq!(bitmap.extend_sorted(100..200000), Ok(199900));
+    assert_eq!(bitmap, (0..200000u32).collect());
+}
+
+#[test]
+fn extend_sorted_not_after_max() {
+    let mut bitmap: RoaringBitmap<u32> = (0..100u32).collect();
+    assert_eq!(bitmap.extend_sorted(vec![99, 100]), Err(NonSortedIntegers { valid_until: 0 }));
+    assert_eq!(bitmap.len(), 100);
+}
+
+#[test]
+fn extend_sorted_keeps_values_before_error() {
+    let mut bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    assert_eq!(bitmap.extend_sorted(vec![5, 70000, 70001, 3]), Err(NonSortedIntegers { valid_until: 3 }));
+    assert_eq!(bitmap, vec![5u32, 70000, 70001].into_iter().collect());
+}
+
+#[test]
+fn extend_mixed_order() {
+    let mut bitmap: RoaringBitmap<u32> = (100000..100010u32).collect();
+    bitmap.extend((0..5000u32).chain(100005..300000).chain(vec![7, 50000, 400000, 3]));
+    let mut expected = (0..5000u32).chain(100000..300000).chain(vec![50000, 400000]).collect::<Vec<u32>>();
+    expected.sort();
+    assert_eq!(bitmap.iter().collect::<Vec<u32>>(), expected);
+    assert_eq!(bitmap.len(), expected.len() as u64);
+}
+
+#[test]
+fn extend_ref_sorted() {
+    let values = (0..100000u32).filter(|i| i % 3 == 0).collect::<Vec<u32>>();
+    let mut bitmap = RoaringBitmap::new();
+    bitmap.extend(&values);
+    assert_eq!(bitmap.iter().collect::<Vec<u32>>(), values);
+}
@@ -0,0 +1,78 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+fn bitmap() -> RoaringBitmap<u32> {
+    let mut bitmap: RoaringBitmap<u32> = (0..2000u32).map(|i| i * 3).collect();
+    bitmap.extend((100000..150000u32).filter(|i| i % 5 != 0));
+    bitmap.insert_range(300000..400000);
+    bitmap.run_optimize();
+    bitmap
+}
+
+#[test]
+fn empty() {
+    let bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    let mut iter = bitmap.into_iter();
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
+
+#[test]
+fn matches_iter() {
+    let bitmap = bitmap();
+    let expected = bitmap.iter().collect::<Vec<u32>>();
+    assert_eq!(bitmap.into_iter().collect::<Vec<u32>>(), expected);
+}
+
+#[test]
+fn matches_iter_reversed() {
+    let bitmap = bitmap();
+    let expected = bitmap.iter().rev().collect::<Vec<u32>>();
+    assert_eq!(bitmap.into_iter().rev().collect::<Vec<u32>>(), expected);
+}
+
+#[test]
+fn exact_len() {
+    let bitmap = bitmap();
+    let len = bitmap.len() as usize;
+    let mut iter = bitmap.into_iter();
+    assert_eq!(iter.len(), len);
+    iter.next();
+    iter.next_back();
+    assert_eq!(iter.len(), len - 2);
+    assert_eq!(iter.size_hint(), (len - 2, Some(len - 2)));
+}
+
+#[test]
+fn both_ends_meet() {
+    let bitmap = bitmap();
+    let expected = bitmap.iter().collect::<Vec<u32>>();
+    let mut iter = bitmap.into_iter();
+    let mut front = Vec::new();
+    let mut back = Vec::new();
+    loop {
+        match iter.next() {
+            Some(value) => front.push(value),
+            None => break,
+        }
+        match iter.next_back() {
+            Some(value) => back.push(value),
+            None => break,
+        }
+    }
+    back.reverse();
+    front.extend(back);
+    assert_eq!(front, expected);
+}
+
+#[test]
+fn for_loop() {
+    let bitmap: RoaringBitmap<u16> = (0..100u16).collect();
+    let mut count = 0;
+    for (i, value) in bitmap.into_iter().enumerate() {
+        assert_eq!(value as usize, i);
+        count += 1;
+    }
+    assert_eq!(count, 100);
+}
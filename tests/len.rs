@@ -0,0 +1,31 @@
+extern crate roaring;
+use roaring::{ RoaringBitmap, RoaringTreemap };
+
+#[test]
+fn full_u16() {
+    let mut bitmap: RoaringBitmap<u16> = RoaringBitmap::new();
+    assert_eq!(bitmap.insert_range(..), 65536);
+    assert_eq!(bitmap.len(), 65536);
+    bitmap.remove(0);
+    assert_eq!(bitmap.len(), 65535);
+}
+
+#[test]
+fn full_u32() {
+    let mut bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    assert_eq!(bitmap.insert_range(..), 1 << 32);
+    assert_eq!(bitmap.len(), 1 << 32);
+    assert_eq!(bitmap.select((1 << 32) - 1), Some(u32::max_value()));
+    bitmap.remove(12345);
+    assert_eq!(bitmap.len(), (1 << 32) - 1);
+    assert_eq!(bitmap.remove_range(..), (1 << 32) - 1);
+    assert_eq!(bitmap.len(), 0);
+}
+
+#[test]
+fn treemap() {
+    let mut treemap = RoaringTreemap::new();
+    treemap.insert(1);
+    treemap.insert(u64::max_value());
+    assert_eq!(treemap.len(), 2u128);
+}
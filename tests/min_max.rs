@@ -0,0 +1,90 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+#[test]
+fn empty() {
+    let bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    assert_eq!(bitmap.min(), None);
+    assert_eq!(bitmap.max(), None);
+    assert_eq!(bitmap.successor(0), None);
+    assert_eq!(bitmap.predecessor(u32::max_value()), None);
+}
+
+#[test]
+fn min_max() {
+    let mut bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    bitmap.insert(0);
+    assert_eq!(bitmap.min(), Some(0));
+    assert_eq!(bitmap.max(), Some(0));
+    bitmap.insert(u32::max_value());
+    assert_eq!(bitmap.min(), Some(0));
+    assert_eq!(bitmap.max(), Some(u32::max_value()));
+    bitmap.insert_range(100000..=200000);
+    bitmap.remove(0);
+    assert_eq!(bitmap.min(), Some(100000));
+}
+
+#[test]
+fn successor_predecessor_array() {
+    let bitmap: RoaringBitmap<u32> = (0..1000u32).map(|i| i * 10 + 5).collect();
+    assert_eq!(bitmap.successor(0), Some(5));
+    assert_eq!(bitmap.successor(5), Some(15));
+    assert_eq!(bitmap.successor(9994), Some(9995));
+    assert_eq!(bitmap.successor(9995), None);
+    assert_eq!(bitmap.predecessor(5), None);
+    assert_eq!(bitmap.predecessor(6), Some(5));
+    assert_eq!(bitmap.predecessor(15), Some(5));
+    assert_eq!(bitmap.predecessor(65536), Some(9995));
+}
+
+#[test]
+fn successor_predecessor_bitmap() {
+    let bitmap: RoaringBitmap<u32> = (0..60000u32).filter(|i| i % 3 == 1).collect();
+    assert_eq!(bitmap.successor(0), Some(1));
+    assert_eq!(bitmap.successor(1), Some(4));
+    assert_eq!(bitmap.successor(62), Some(64));
+    assert_eq!(bitmap.successor(59998), None);
+    assert_eq!(bitmap.predecessor(1), None);
+    assert_eq!(bitmap.predecessor(64), Some(61));
+    assert_eq!(bitmap.predecessor(65), Some(64));
+    assert_eq!(bitmap.predecessor(u32::max_value()), Some(59998));
+}
+
+#[test]
+fn successor_predecessor_run() {
+    let mut bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    bitmap.insert_range(10..20);
+    bitmap.insert_range(30..40);
+    bitmap.run_optimize();
+    assert_eq!(bitmap.successor(5), Some(10));
+    assert_eq!(bitmap.successor(10), Some(11));
+    assert_eq!(bitmap.successor(19), Some(30));
+    assert_eq!(bitmap.successor(25), Some(30));
+    assert_eq!(bitmap.successor(39), None);
+    assert_eq!(bitmap.predecessor(10), None);
+    assert_eq!(bitmap.predecessor(15), Some(14));
+    assert_eq!(bitmap.predecessor(30), Some(19));
+    assert_eq!(bitmap.predecessor(100), Some(39));
+}
+
+#[test]
+fn successor_predecessor_across_containers() {
+    let bitmap: RoaringBitmap<u32> = vec![65535u32, 65536, 300000, u32::max_value()].into_iter().collect();
+    assert_eq!(bitmap.successor(65535), Some(65536));
+    assert_eq!(bitmap.successor(65536), Some(300000));
+    assert_eq!(bitmap.successor(300000), Some(u32::max_value()));
+    assert_eq!(bitmap.successor(u32::max_value()), None);
+    assert_eq!(bitmap.predecessor(65536), Some(65535));
+    assert_eq!(bitmap.predecessor(300000), Some(65536));
+    assert_eq!(bitmap.predecessor(u32::max_value()), Some(300000));
+    assert_eq!(bitmap.predecessor(65535), None);
+}
+
+#[test]
+fn u16() {
+    let bitmap: RoaringBitmap<u16> = vec![0u16, 1000, u16::max_value()].into_iter().collect();
+    assert_eq!(bitmap.min(), Some(0));
+    assert_eq!(bitmap.max(), Some(u16::max_value()));
+    assert_eq!(bitmap.successor(0), Some(1000));
+    assert_eq!(bitmap.predecessor(u16::max_value()), Some(1000));
+}
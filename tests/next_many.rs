@@ -0,0 +1,74 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+#[test]
+fn next_many_spans_containers() {
+    let bitmap: RoaringBitmap<u32> = vec![1u32, 2, 65535, 65536, 200000].into_iter().collect();
+    let mut iter = bitmap.iter();
+    let mut buffer = [0u32; 3];
+    assert_eq!(iter.next_many(&mut buffer), 3);
+    assert_eq!(buffer, [1, 2, 65535]);
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.next_many(&mut buffer), 2);
+    assert_eq!(&buffer[..2], &[65536, 200000]);
+    assert_eq!(iter.next_many(&mut buffer), 0);
+}
+
+#[test]
+fn next_many_bitmap_container() {
+    let bitmap: RoaringBitmap<u32> = (0..60000u32).filter(|i| i % 5 != 0).collect();
+    let mut iter = bitmap.iter();
+    let mut buffer = vec![0u32; 1000];
+    assert_eq!(iter.next_many(&mut buffer), 1000);
+    assert_eq!(buffer[999], 1249);
+    assert_eq!(iter.len(), 47000);
+    let mut rest = vec![0u32; 50000];
+    assert_eq!(iter.next_many(&mut rest), 47000);
+    assert_eq!(&rest[..3], &[1251, 1252, 1253]);
+    assert_eq!(rest[46999], 59999);
+}
+
+#[test]
+fn next_many_run_container() {
+    let mut bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    bitmap.insert_range(0..10);
+    bitmap.insert_range(1000..3000);
+    bitmap.run_optimize();
+    let mut iter = bitmap.iter();
+    let mut buffer = [0u32; 12];
+    assert_eq!(iter.next_many(&mut buffer), 12);
+    assert_eq!(buffer, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 1000, 1001]);
+    let mut rest = vec![0u32; 5000];
+    assert_eq!(iter.next_many(&mut rest), 1998);
+    assert_eq!(rest[1997], 2999);
+}
+
+#[test]
+fn next_many_after_next_back() {
+    let bitmap: RoaringBitmap<u32> = vec![1u32, 2, 65535, 65536, 200000].into_iter().collect();
+    let mut iter = bitmap.iter();
+    assert_eq!(iter.next_back(), Some(200000));
+    assert_eq!(iter.next_back(), Some(65536));
+    let mut buffer = [0u32; 5];
+    assert_eq!(iter.next_many(&mut buffer), 3);
+    assert_eq!(&buffer[..3], &[1, 2, 65535]);
+}
+
+#[test]
+fn to_vec() {
+    let mut bitmap: RoaringBitmap<u32> = vec![1u32, 70000].into_iter().collect();
+    bitmap.insert_range(200000..200003);
+    assert_eq!(bitmap.to_vec(), vec![1, 70000, 200000, 200001, 200002]);
+    assert_eq!(RoaringBitmap::<u32>::new().to_vec(), Vec::<u32>::new());
+}
+
+#[test]
+fn copy_to_slice() {
+    let bitmap: RoaringBitmap<u32> = (0..60000u32).filter(|i| i % 5 != 0).collect();
+    let mut buffer = [0u32; 5];
+    assert_eq!(bitmap.copy_to_slice(&mut buffer), 5);
+    assert_eq!(buffer, [1, 2, 3, 4, 6]);
+    let mut buffer = vec![0u32; 50000];
+    assert_eq!(bitmap.copy_to_slice(&mut buffer), 48000);
+    assert_eq!(buffer[47999], 59999);
+}
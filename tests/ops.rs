@@ -1,5 +1,7 @@
 extern crate roaring;
 
+use std::ops::BitOrAssign;
+
 use roaring::RoaringBitmap;
 
 #[test]
@@ -44,3 +46,83 @@ fn xor() {
     assert_eq!(rb4, rb1 ^ rb2 ^ rb3);
 }
 
+
+fn sets() -> (RoaringBitmap<u32>, RoaringBitmap<u32>) {
+    let mut rb1: RoaringBitmap<u32> = (0..100000u32).filter(|i| i % 3 == 0).collect();
+    rb1.insert_range(300000..400000);
+    let mut rb2: RoaringBitmap<u32> = (50000..250000u32).filter(|i| i % 5 == 0).collect();
+    rb2.insert_range(350000..500000);
+    (rb1, rb2)
+}
+
+#[test]
+fn or_assign() {
+    let (rb1, rb2) = sets();
+    let expected = &rb1 | &rb2;
+
+    let mut owned = rb1.clone();
+    owned |= rb2.clone();
+    assert_eq!(owned, expected);
+
+    let mut borrowed = rb1.clone();
+    borrowed |= &rb2;
+    assert_eq!(borrowed, expected);
+}
+
+#[test]
+fn and_assign() {
+    let (rb1, rb2) = sets();
+    let expected = &rb1 & &rb2;
+
+    let mut owned = rb1.clone();
+    owned &= rb2.clone();
+    assert_eq!(owned, expected);
+
+    let mut borrowed = rb1.clone();
+    borrowed &= &rb2;
+    assert_eq!(borrowed, expected);
+}
+
+#[test]
+fn sub_assign() {
+    let (rb1, rb2) = sets();
+    let expected = &rb1 - &rb2;
+
+    let mut owned = rb1.clone();
+    owned -= rb2.clone();
+    assert_eq!(owned, expected);
+
+    let mut borrowed = rb1.clone();
+    borrowed -= &rb2;
+    assert_eq!(borrowed, expected);
+}
+
+#[test]
+fn xor_assign() {
+    let (rb1, rb2) = sets();
+    let expected = &rb1 ^ &rb2;
+
+    let mut owned = rb1.clone();
+    owned ^= rb2.clone();
+    assert_eq!(owned, expected);
+
+    let mut borrowed = rb1.clone();
+    borrowed ^= &rb2;
+    assert_eq!(borrowed, expected);
+
+    let mut cancelled = rb1.clone();
+    cancelled ^= rb1;
+    assert_eq!(cancelled.is_empty(), true);
+}
+
+#[test]
+fn assign_generic() {
+    fn or_all<T: for<'a> BitOrAssign<&'a T>>(mut result: T, items: &[T]) -> T {
+        for item in items {
+            result |= item;
+        }
+        result
+    }
+    let (rb1, rb2) = sets();
+    assert_eq!(or_all(RoaringBitmap::new(), &[rb1.clone(), rb2.clone()]), rb1 | rb2);
+}
@@ -0,0 +1,95 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+#[test]
+fn array_and_bitmap() {
+    let array: RoaringBitmap<u32> = (0..1000u32).map(|i| i * 37).chain(vec![200000]).collect();
+    let bitmap: RoaringBitmap<u32> = (0..30000u32).map(|i| i * 2).collect();
+    let in_array = |i: u32| (i % 37 == 0 && i < 37000) || i == 200000;
+    let in_bitmap = |i: u32| i % 2 == 0 && i < 60000;
+
+    assert_eq!(array.clone() | bitmap.clone(), (0..200001u32).filter(|&i| in_array(i) || in_bitmap(i)).collect());
+    assert_eq!(array.clone() & bitmap.clone(), (0..37000u32).filter(|&i| in_array(i) && in_bitmap(i)).collect());
+    assert_eq!(array.clone() - bitmap.clone(), (0..200001u32).filter(|&i| in_array(i) && !in_bitmap(i)).collect());
+    assert_eq!(bitmap.clone() - array.clone(), (0..60000u32).filter(|&i| in_bitmap(i) && !in_array(i)).collect());
+    assert_eq!(bitmap.clone() ^ array.clone(), (0..200001u32).filter(|&i| in_array(i) != in_bitmap(i)).collect());
+}
+
+#[test]
+fn bitmap_and_run() {
+    let bitmap: RoaringBitmap<u32> = (0..30000u32).map(|i| i * 2).collect();
+    let mut run: RoaringBitmap<u32> = RoaringBitmap::new();
+    run.insert_range(1000..40000);
+    run.insert_range(70000..70010);
+    run.run_optimize();
+    let in_bitmap = |i: u32| i % 2 == 0 && i < 60000;
+    let in_run = |i: u32| (i >= 1000 && i < 40000) || (i >= 70000 && i < 70010);
+
+    assert_eq!(run.clone() | bitmap.clone(), (0..70010u32).filter(|&i| in_bitmap(i) || in_run(i)).collect());
+    assert_eq!(run.clone() & bitmap.clone(), (1000..40000u32).filter(|&i| in_bitmap(i)).collect());
+    assert_eq!(run.clone() - bitmap.clone(), (0..70010u32).filter(|&i| in_run(i) && !in_bitmap(i)).collect());
+    assert_eq!(bitmap.clone() - run.clone(), (0..60000u32).filter(|&i| in_bitmap(i) && !in_run(i)).collect());
+    assert_eq!(run.clone() ^ bitmap.clone(), (0..70010u32).filter(|&i| in_bitmap(i) != in_run(i)).collect());
+}
+
+#[test]
+fn array_and_run() {
+    let array: RoaringBitmap<u32> = vec![5u32, 1500, 39999, 40000, 70005].into_iter().collect();
+    let mut run: RoaringBitmap<u32> = RoaringBitmap::new();
+    run.insert_range(1000..40000);
+    run.run_optimize();
+
+    assert_eq!((array.clone() | run.clone()).len(), 39003);
+    assert_eq!(array.clone() & run.clone(), vec![1500u32, 39999].into_iter().collect());
+    assert_eq!(array.clone() - run.clone(), vec![5u32, 40000, 70005].into_iter().collect());
+    assert_eq!(run.clone() - array.clone(), (1000..40000u32).filter(|&i| i != 1500 && i != 39999).collect());
+    assert_eq!(array.clone() ^ run.clone(), (1000..40000u32).filter(|&i| i != 1500 && i != 39999).chain(vec![5, 40000, 70005]).collect());
+}
+
+#[test]
+fn assign_ops() {
+    let set1: RoaringBitmap<u32> = (0..1000u32).map(|i| i * 37).chain(100000..140000).collect();
+    let set2: RoaringBitmap<u32> = (0..30000u32).map(|i| i * 2).chain(120000..130000).collect();
+
+    let mut assigned = set1.clone();
+    assigned |= set2.clone();
+    assert_eq!(assigned, &set1 | &set2);
+
+    let mut assigned = set1.clone();
+    assigned &= set2.clone();
+    assert_eq!(assigned, &set1 & &set2);
+
+    let mut assigned = set1.clone();
+    assigned -= set2.clone();
+    assert_eq!(assigned, &set1 - &set2);
+
+    let mut assigned = set1.clone();
+    assigned ^= set2.clone();
+    assert_eq!(assigned, &set1 ^ &set2);
+}
+
+#[test]
+fn owned_results_match_borrowed() {
+    let set1: RoaringBitmap<u32> = (0..1000u32).map(|i| i * 37).chain(100000..140000).collect();
+    let set2: RoaringBitmap<u32> = (0..30000u32).map(|i| i * 2).chain(120000..130000).collect();
+    assert_eq!(set1.clone() | set2.clone(), set1.union(&set2).collect());
+    assert_eq!(set1.clone() & set2.clone(), set1.intersection(&set2).collect());
+    assert_eq!(set1.clone() - set2.clone(), set1.difference(&set2).collect());
+    assert_eq!(set1.clone() ^ set2.clone(), set1.symmetric_difference(&set2).collect());
+    assert_eq!(&set1 | &set2, set1.union(&set2).collect());
+    assert_eq!(&set1 & &set2, set1.intersection(&set2).collect());
+    assert_eq!(&set1 - &set2, set1.difference(&set2).collect());
+    assert_eq!(&set1 ^ &set2, set1.symmetric_difference(&set2).collect());
+}
+
+#[test]
+fn owned_with_empty() {
+    let set: RoaringBitmap<u32> = (0..1000u32).map(|i| i * 37).chain(100000..140000).collect();
+    let empty = RoaringBitmap::new();
+    assert_eq!(set.clone() | empty.clone(), set);
+    assert_eq!(empty.clone() | set.clone(), set);
+    assert_eq!((set.clone() & empty.clone()).is_empty(), true);
+    assert_eq!(set.clone() - empty.clone(), set);
+    assert_eq!((empty.clone() - set.clone()).is_empty(), true);
+    assert_eq!(empty ^ set.clone(), set);
+}
@@ -0,0 +1,84 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+#[test]
+fn insert_range_empty() {
+    let mut bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    assert_eq!(bitmap.insert_range(10..10), 0);
+    let (start, end) = (10, 5);
+    assert_eq!(bitmap.insert_range(start..end), 0);
+    assert_eq!(bitmap.is_empty(), true);
+}
+
+#[test]
+fn insert_range_within_container() {
+    let mut bitmap: RoaringBitmap<u32> = (0..2000u32).filter(|i| i % 2 == 0).collect();
+    assert_eq!(bitmap.insert_range(500..1500), 500);
+    let expected: RoaringBitmap<u32> = (0..2000u32).filter(|&i| i % 2 == 0 || (i >= 500 && i < 1500)).collect();
+    assert_eq!(bitmap, expected);
+}
+
+#[test]
+fn insert_range_spanning_containers() {
+    let mut bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    bitmap.insert(70000);
+    bitmap.insert(300000);
+    assert_eq!(bitmap.insert_range(60000..=200000), 140000);
+    assert_eq!(bitmap.len(), 140002);
+    assert_eq!(bitmap, (60000..200001u32).chain(300000..300001).collect());
+}
+
+#[test]
+fn insert_range_full() {
+    let mut bitmap: RoaringBitmap<u16> = RoaringBitmap::new();
+    bitmap.insert(100);
+    assert_eq!(bitmap.insert_range(..), 65535);
+    assert_eq!(bitmap.contains(0), true);
+    assert_eq!(bitmap.contains(65535), true);
+    assert_eq!(bitmap.insert_range(..), 0);
+}
+
+#[test]
+fn remove_range_empty() {
+    let mut bitmap: RoaringBitmap<u32> = (0..100u32).collect();
+    assert_eq!(bitmap.remove_range(50..50), 0);
+    assert_eq!(bitmap.remove_range(200..300), 0);
+    assert_eq!(bitmap.len(), 100);
+}
+
+#[test]
+fn remove_range_within_container() {
+    let mut bitmap: RoaringBitmap<u32> = (0..10000u32).collect();
+    assert_eq!(bitmap.remove_range(1000..9000), 8000);
+    assert_eq!(bitmap, (0..1000u32).chain(9000..10000).collect());
+}
+
+#[test]
+fn remove_range_spanning_containers() {
+    let mut bitmap: RoaringBitmap<u32> = (0..300000u32).filter(|i| i % 3 == 0).collect();
+    assert_eq!(bitmap.remove_range(1..=299998), 99999);
+    assert_eq!(bitmap, vec![0u32].into_iter().collect());
+}
+
+#[test]
+fn remove_range_all() {
+    let mut bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    bitmap.insert_range(100000..500000);
+    assert_eq!(bitmap.remove_range(..), 400000);
+    assert_eq!(bitmap.is_empty(), true);
+}
+
+#[test]
+fn ranges_match_single_values() {
+    let mut ranged: RoaringBitmap<u32> = (0..20000u32).filter(|i| i % 7 == 0).collect();
+    let mut single = ranged.clone();
+    ranged.insert_range(3000..70000);
+    ranged.remove_range(65000..=65600);
+    for i in 3000..70000 {
+        single.insert(i);
+    }
+    for i in 65000..65601 {
+        single.remove(i);
+    }
+    assert_eq!(ranged, single);
+}
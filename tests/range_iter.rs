@@ -0,0 +1,53 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+#[test]
+fn range_within_array() {
+    let bitmap: RoaringBitmap<u32> = (0..1000u32).map(|i| i * 3).collect();
+    assert_eq!(bitmap.range(10..20).collect::<Vec<u32>>(), vec![12, 15, 18]);
+    assert_eq!(bitmap.range(9..=18).len(), 4);
+    assert_eq!(bitmap.range(9..=18).rev().collect::<Vec<u32>>(), vec![18, 15, 12, 9]);
+    assert_eq!(bitmap.range(10..12).next(), None);
+}
+
+#[test]
+fn range_across_containers() {
+    let bitmap: RoaringBitmap<u32> = vec![5u32, 65534, 65535, 65536, 65537, 200000].into_iter().collect();
+    assert_eq!(bitmap.range(65535..65537).collect::<Vec<u32>>(), vec![65535, 65536]);
+    assert_eq!(bitmap.range(6..200000).collect::<Vec<u32>>(), vec![65534, 65535, 65536, 65537]);
+    assert_eq!(bitmap.range(6..=200000).rev().collect::<Vec<u32>>(), vec![200000, 65537, 65536, 65535, 65534]);
+    assert_eq!(bitmap.range(70000..200000).next(), None);
+}
+
+#[test]
+fn range_inside_run() {
+    let mut bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    bitmap.insert_range(0..10);
+    bitmap.insert_range(1000..3000);
+    bitmap.run_optimize();
+    assert_eq!(bitmap.range(1500..1503).collect::<Vec<u32>>(), vec![1500, 1501, 1502]);
+    assert_eq!(bitmap.range(5..1002).collect::<Vec<u32>>(), vec![5, 6, 7, 8, 9, 1000, 1001]);
+    assert_eq!(bitmap.range(5..1002).rev().collect::<Vec<u32>>(), vec![1001, 1000, 9, 8, 7, 6, 5]);
+    assert_eq!(bitmap.range(10..1000).next(), None);
+    assert_eq!(bitmap.range(..).len(), 2010);
+}
+
+#[test]
+fn unbounded() {
+    let bitmap: RoaringBitmap<u32> = vec![0u32, 5, 100000, u32::max_value()].into_iter().collect();
+    assert_eq!(bitmap.range(..).collect::<Vec<u32>>(), vec![0, 5, 100000, u32::max_value()]);
+    assert_eq!(bitmap.range(5..).collect::<Vec<u32>>(), vec![5, 100000, u32::max_value()]);
+    assert_eq!(bitmap.range(..=5).collect::<Vec<u32>>(), vec![0, 5]);
+    let (start, end) = (6, 5);
+    assert_eq!(bitmap.range(start..end).next(), None);
+}
+
+#[test]
+fn both_ends() {
+    let bitmap: RoaringBitmap<u32> = (0..60000u32).filter(|i| i % 5 != 0).collect();
+    let mut iter = bitmap.range(1000..1010);
+    assert_eq!(iter.next(), Some(1001));
+    assert_eq!(iter.next_back(), Some(1009));
+    assert_eq!(iter.len(), 6);
+    assert_eq!(iter.collect::<Vec<u32>>(), vec![1002, 1003, 1004, 1006, 1007, 1008]);
+}
@@ -0,0 +1,76 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+// A bitmap with array, bitmap and run containers, and gaps between them.
+fn bitmap() -> RoaringBitmap<u32> {
+    let mut bitmap: RoaringBitmap<u32> = (0..2000u32).map(|i| i * 7).collect();
+    bitmap.extend((131072..196608u32).filter(|i| i % 3 != 0));
+    bitmap.insert_range(300000..420000);
+    bitmap.run_optimize();
+    bitmap
+}
+
+const RANGES: &'static [(u32, u32)] = &[
+    (0, 0), (0, 1), (5, 14000), (131073, 196000), (65536, 131072), (250000, 260000),
+    (300000, 420000), (310000, 400000), (299999, 400000), (10, 500000), (500000, 600000),
+];
+
+#[test]
+fn intersect_with_range() {
+    for &(start, end) in RANGES {
+        let mut bitmap = bitmap();
+        let expected = bitmap.iter().filter(|&value| value >= start && value < end).collect::<Vec<u32>>();
+        bitmap.intersect_with_range(start..end);
+        assert_eq!(bitmap.iter().collect::<Vec<u32>>(), expected);
+        assert_eq!(bitmap.len(), expected.len() as u64);
+    }
+}
+
+#[test]
+fn intersect_with_full_range() {
+    let mut bitmap = bitmap();
+    bitmap.intersect_with_range(..);
+    assert_eq!(bitmap, self::bitmap());
+}
+
+#[test]
+fn contains_range() {
+    let bitmap = bitmap();
+    for &(start, end) in RANGES {
+        let expected = (start..end).all(|value| bitmap.contains(value));
+        assert_eq!(bitmap.contains_range(start..end), expected, "{}..{}", start, end);
+    }
+    assert_eq!(bitmap.contains_range(300000..=419999), true);
+    assert_eq!(bitmap.contains_range(300000..=420000), false);
+}
+
+#[test]
+fn contains_range_full_containers() {
+    let mut bitmap = RoaringBitmap::<u32>::new();
+    bitmap.insert_range(65536..=327679);
+    assert_eq!(bitmap.contains_range(65536..327680), true);
+    assert_eq!(bitmap.contains_range(65535..327680), false);
+    bitmap.remove(200000);
+    assert_eq!(bitmap.contains_range(65536..327680), false);
+}
+
+#[test]
+fn intersects_range() {
+    let bitmap = bitmap();
+    for &(start, end) in RANGES {
+        let expected = (start..end).any(|value| bitmap.contains(value));
+        assert_eq!(bitmap.intersects_range(start..end), expected, "{}..{}", start, end);
+    }
+    assert_eq!(bitmap.intersects_range(1..7), false);
+    assert_eq!(bitmap.intersects_range(1..=7), true);
+}
+
+#[test]
+fn range_cardinality() {
+    let bitmap = bitmap();
+    for &(start, end) in RANGES {
+        let expected = bitmap.iter().filter(|&value| value >= start && value < end).count() as u64;
+        assert_eq!(bitmap.range_cardinality(start..end), expected, "{}..{}", start, end);
+    }
+    assert_eq!(bitmap.range_cardinality(..), bitmap.len());
+}
@@ -0,0 +1,95 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+#[test]
+fn empty() {
+    let bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    assert_eq!(bitmap.rank(0), 0);
+    assert_eq!(bitmap.rank(u32::max_value()), 0);
+    assert_eq!(bitmap.select(0), None);
+}
+
+#[test]
+fn array() {
+    let values: Vec<u32> = (0..3000u32).map(|i| i * 7).collect();
+    let bitmap: RoaringBitmap<u32> = values.iter().cloned().collect();
+    for (n, &value) in values.iter().enumerate() {
+        assert_eq!(bitmap.rank(value), n as u64 + 1);
+        assert_eq!(bitmap.rank(value + 1), n as u64 + 1);
+        assert_eq!(bitmap.select(n as u64), Some(value));
+    }
+    assert_eq!(bitmap.select(3000), None);
+}
+
+#[test]
+fn bitmap() {
+    let values: Vec<u32> = (0..60000u32).filter(|i| i % 3 != 0).collect();
+    let bitmap: RoaringBitmap<u32> = values.iter().cloned().collect();
+    for (n, &value) in values.iter().enumerate() {
+        assert_eq!(bitmap.rank(value), n as u64 + 1);
+        assert_eq!(bitmap.select(n as u64), Some(value));
+    }
+    assert_eq!(bitmap.rank(65535), 40000);
+    assert_eq!(bitmap.select(40000), None);
+}
+
+#[test]
+fn run() {
+    let mut bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    bitmap.insert_range(10..20);
+    bitmap.insert_range(100..200);
+    bitmap.insert_range(1000..1100);
+    bitmap.run_optimize();
+    assert_eq!(bitmap.rank(9), 0);
+    assert_eq!(bitmap.rank(15), 6);
+    assert_eq!(bitmap.rank(19), 10);
+    assert_eq!(bitmap.rank(50), 10);
+    assert_eq!(bitmap.rank(100), 11);
+    assert_eq!(bitmap.rank(150), 61);
+    assert_eq!(bitmap.rank(5000), 210);
+    assert_eq!(bitmap.select(0), Some(10));
+    assert_eq!(bitmap.select(10), Some(100));
+    assert_eq!(bitmap.select(209), Some(1099));
+    assert_eq!(bitmap.select(210), None);
+}
+
+#[test]
+fn across_containers() {
+    let mut bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    bitmap.insert_range(0..65536);
+    bitmap.insert(100000);
+    bitmap.insert_range(200000..210000);
+    assert_eq!(bitmap.rank(65535), 65536);
+    assert_eq!(bitmap.rank(99999), 65536);
+    assert_eq!(bitmap.rank(100000), 65537);
+    assert_eq!(bitmap.rank(205000), 70538);
+    assert_eq!(bitmap.select(65536), Some(100000));
+    assert_eq!(bitmap.select(65537), Some(200000));
+    assert_eq!(bitmap.select(75536), Some(209999));
+    assert_eq!(bitmap.select(75537), None);
+}
+
+#[test]
+fn u16() {
+    let bitmap: RoaringBitmap<u16> = (0..1000u16).map(|i| i * 50).collect();
+    assert_eq!(bitmap.rank(49), 1);
+    assert_eq!(bitmap.rank(u16::max_value()), 1000);
+    assert_eq!(bitmap.select(999), Some(49950));
+}
+
+#[test]
+fn after_mutation() {
+    let mut bitmap: RoaringBitmap<u32> = vec![1u32, 100000, 200000].into_iter().collect();
+    assert_eq!(bitmap.rank(200000), 3);
+    assert_eq!(bitmap.select(2), Some(200000));
+    bitmap.insert(70000);
+    assert_eq!(bitmap.rank(200000), 4);
+    assert_eq!(bitmap.select(2), Some(100000));
+    bitmap.remove_range(0..150000);
+    assert_eq!(bitmap.rank(200000), 1);
+    assert_eq!(bitmap.select(0), Some(200000));
+    assert_eq!(bitmap.select(1), None);
+    bitmap |= &(0..10u32).collect();
+    assert_eq!(bitmap.rank(200000), 11);
+    assert_eq!(bitmap.select(10), Some(200000));
+}
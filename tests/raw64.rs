@@ -0,0 +1,86 @@
+extern crate roaring;
+
+use roaring::{ RoaringBitmap, DeserializeError };
+
+fn bitmaps() -> Vec<RoaringBitmap<u32>> {
+    vec![
+        RoaringBitmap::new(),
+        (0..2000u32).map(|i| i * 3).collect(),
+        (0..6000u32).chain(1_000_000..1_012_000).collect(),
+        (0..65536u32).chain(200_000..200_010).collect(),
+    ]
+}
+
+#[test]
+fn roundtrip() {
+    for bitmap in bitmaps() {
+        assert_eq!(RoaringBitmap::try_from_raw64(&bitmap.to_raw64()).unwrap(), bitmap);
+        assert_eq!(RoaringBitmap::from_raw64(bitmap.to_raw64()), bitmap);
+    }
+}
+
+#[test]
+fn roundtrip_optimized() {
+    let mut bitmap: RoaringBitmap<u32> = (0..30000u32).chain(100_000..100_100).collect();
+    bitmap.run_optimize();
+    assert_eq!(RoaringBitmap::try_from_raw64(&bitmap.to_raw64()).unwrap(), bitmap);
+}
+
+#[test]
+fn roundtrip_u16() {
+    let bitmap: RoaringBitmap<u16> = (0..300u16).chain(1000..1010).collect();
+    assert_eq!(RoaringBitmap::try_from_raw64(&bitmap.to_raw64()).unwrap(), bitmap);
+}
+
+#[test]
+fn truncated() {
+    for bitmap in bitmaps().into_iter().skip(1) {
+        let raw64 = bitmap.to_raw64();
+        for len in 0..raw64.len() {
+            match RoaringBitmap::<u32>::try_from_raw64(&raw64[..len]) {
+                Err(DeserializeError::UnexpectedEnd) => (),
+                other => panic!("{:?}", other),
+            }
+        }
+    }
+}
+
+#[test]
+fn trailing() {
+    let mut raw64 = RoaringBitmap::<u32>::new().to_raw64();
+    raw64.push(0);
+    match RoaringBitmap::<u32>::try_from_raw64(&raw64) {
+        Err(DeserializeError::TrailingData) => (),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+#[should_panic]
+fn from_raw64_panics() {
+    RoaringBitmap::<u32>::from_raw64(vec![1, 0]);
+}
+
+fn assert_invalid<F: Fn(&DeserializeError) -> bool>(raw64: Vec<u64>, expected: F) {
+    match RoaringBitmap::<u32>::try_from_raw64(&raw64) {
+        Err(ref error) if expected(error) => (),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn invalid() {
+    // key, len, type, size, values...
+    assert_invalid(vec![1, 0, 2, 7, 2, 1, 2], |e| match *e { DeserializeError::InvalidStoreType(7) => true, _ => false });
+    assert_invalid(vec![1, 70000, 1, 0, 1, 5], |e| match *e { DeserializeError::ValueOutOfRange(70000) => true, _ => false });
+    assert_invalid(vec![1, 0, 1, 0, 1, 70000], |e| match *e { DeserializeError::ValueOutOfRange(70000) => true, _ => false });
+    assert_invalid(vec![1, 0, 2, 0, 2, 5, 3], |e| match *e { DeserializeError::UnsortedValues => true, _ => false });
+    assert_invalid(vec![1, 0, 2, 0, 2, 5, 5], |e| match *e { DeserializeError::UnsortedValues => true, _ => false });
+    assert_invalid(vec![1, 0, 3, 0, 2, 1, 2], |e| match *e { DeserializeError::InvalidCardinality { expected: 3, actual: 2 } => true, _ => false });
+    assert_invalid(vec![1, 0, 0, 0, 0], |e| match *e { DeserializeError::EmptyContainer => true, _ => false });
+    assert_invalid(vec![2, 5, 1, 0, 1, 1, 4, 1, 0, 1, 1], |e| match *e { DeserializeError::UnsortedKeys => true, _ => false });
+    assert_invalid(vec![1, 0, 1, 1, 2, 1, 0], |e| match *e { DeserializeError::InvalidBitmapSize(2) => true, _ => false });
+    assert_invalid(vec![1, 0, 4, 2, 4, 10, 1, 5, 1], |e| match *e { DeserializeError::InvalidRuns => true, _ => false });
+    assert_invalid(vec![1, 0, 4, 2, 4, 10, 1, 12, 1], |e| match *e { DeserializeError::InvalidRuns => true, _ => false });
+    assert_invalid(vec![1, 0, 2, 2, 2, 65535, 1], |e| match *e { DeserializeError::ValueOutOfRange(65536) => true, _ => false });
+}
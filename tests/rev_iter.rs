@@ -0,0 +1,89 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+#[test]
+fn rev_across_container_boundary() {
+    let bitmap: RoaringBitmap<u32> = vec![5u32, 65535, 65536, 200000].into_iter().collect();
+    assert_eq!(bitmap.rev_iter().collect::<Vec<u32>>(), vec![200000, 65536, 65535, 5]);
+    assert_eq!(bitmap.iter().rev().collect::<Vec<u32>>(), vec![200000, 65536, 65535, 5]);
+}
+
+#[test]
+fn rev_bitmap_container() {
+    let bitmap: RoaringBitmap<u32> = (0..10000u32).filter(|i| i % 3 != 0).collect();
+    assert_eq!(bitmap.rev_iter().collect::<Vec<u32>>(), (0..10000u32).rev().filter(|i| i % 3 != 0).collect::<Vec<u32>>());
+}
+
+#[test]
+fn rev_run_container() {
+    let mut bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    bitmap.insert_range(10..20);
+    bitmap.insert_range(65530..65546);
+    bitmap.run_optimize();
+    assert_eq!(bitmap.rev_iter().collect::<Vec<u32>>(), (10..20u32).chain(65530..65546).rev().collect::<Vec<u32>>());
+}
+
+#[test]
+fn rev_empty() {
+    let bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    assert_eq!(bitmap.rev_iter().next(), None);
+}
+
+#[test]
+fn top_k() {
+    let bitmap: RoaringBitmap<u32> = (0..300000u32).filter(|i| i % 7 == 0).collect();
+    assert_eq!(bitmap.rev_iter().take(3).collect::<Vec<u32>>(), vec![299999, 299992, 299985]);
+}
+
+#[test]
+fn both_ends_meet_in_array() {
+    let bitmap: RoaringBitmap<u32> = (0..100u32).map(|i| i * 3).collect();
+    let mut iter = bitmap.iter();
+    assert_eq!(iter.by_ref().take(40).collect::<Vec<u32>>(), (0..40u32).map(|i| i * 3).collect::<Vec<u32>>());
+    assert_eq!(iter.by_ref().rev().take(50).collect::<Vec<u32>>(), (50..100u32).rev().map(|i| i * 3).collect::<Vec<u32>>());
+    assert_eq!(iter.len(), 10);
+    assert_eq!(iter.collect::<Vec<u32>>(), (40..50u32).map(|i| i * 3).collect::<Vec<u32>>());
+}
+
+#[test]
+fn both_ends_meet_in_bitmap() {
+    let bitmap: RoaringBitmap<u32> = (0..10000u32).filter(|i| i % 3 != 0).collect();
+    let mut iter = bitmap.iter();
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(9998));
+    assert_eq!(iter.by_ref().take(3000).count(), 3000);
+    assert_eq!(iter.by_ref().rev().take(3664).count(), 3664);
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
+
+#[test]
+fn both_ends_meet_in_run() {
+    let mut bitmap: RoaringBitmap<u32> = RoaringBitmap::new();
+    bitmap.insert_range(1000..3000);
+    bitmap.run_optimize();
+    let mut iter = bitmap.iter();
+    assert_eq!(iter.by_ref().take(500).last(), Some(1499));
+    assert_eq!(iter.by_ref().rev().take(500).last(), Some(2500));
+    assert_eq!(iter.len(), 1000);
+    assert_eq!(iter.next(), Some(1500));
+    assert_eq!(iter.next_back(), Some(2499));
+    assert_eq!(iter.by_ref().rev().count(), 998);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
+
+#[test]
+fn both_ends_across_containers() {
+    let bitmap: RoaringBitmap<u32> = vec![1u32, 2, 70000, 140000, 140001].into_iter().collect();
+    let mut iter = bitmap.iter();
+    assert_eq!(iter.next_back(), Some(140001));
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(140000));
+    assert_eq!(iter.next_back(), Some(70000));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
@@ -0,0 +1,117 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+#[test]
+fn full() {
+    let mut bitmap: RoaringBitmap<u32> = (0..65536u32).collect();
+    assert_eq!(bitmap.len(), 65536);
+    assert_eq!(bitmap.contains(0), true);
+    assert_eq!(bitmap.contains(65535), true);
+    assert_eq!(bitmap.contains(65536), false);
+    assert_eq!(bitmap.insert(1000), false);
+
+    assert_eq!(bitmap.remove(1000), true);
+    assert_eq!(bitmap.remove(1000), false);
+    assert_eq!(bitmap.remove(0), true);
+    assert_eq!(bitmap.remove(65535), true);
+    assert_eq!(bitmap.len(), 65533);
+    assert_eq!(bitmap.contains(999), true);
+    assert_eq!(bitmap.contains(1000), false);
+    assert_eq!(bitmap.contains(1001), true);
+
+    assert_eq!(bitmap.insert(1000), true);
+    assert_eq!(bitmap, (1..65535u32).collect::<RoaringBitmap<u32>>());
+}
+
+#[test]
+fn full_u16() {
+    let mut bitmap: RoaringBitmap<u16> = (0..512u16).collect();
+    assert_eq!(bitmap.len(), 512);
+    for i in (0..512u16).filter(|i| i % 3 == 0) {
+        assert_eq!(bitmap.remove(i), true);
+    }
+    assert_eq!(bitmap, (0..512u16).filter(|i| i % 3 != 0).collect::<RoaringBitmap<u16>>());
+}
+
+#[test]
+fn many_runs_to_bitmap() {
+    let mut bitmap: RoaringBitmap<u32> = (0..65536u32).collect();
+    for i in (0..65536u32).filter(|i| i % 4 == 0) {
+        bitmap.remove(i);
+    }
+    assert_eq!(bitmap.len(), 49152);
+    assert_eq!(bitmap, (0..65536u32).filter(|i| i % 4 != 0).collect::<RoaringBitmap<u32>>());
+}
+
+#[test]
+fn mostly_full_is_run() {
+    let mut bitmap: RoaringBitmap<u32> = (0..60000u32).filter(|&i| i != 30000).collect();
+    assert_eq!(bitmap.run_optimize(), false);
+    assert_eq!(bitmap.remove_run_compression(), true);
+
+    let mut bitmap: RoaringBitmap<u32> = (0..60000u32).filter(|i| i % 2 == 0).collect();
+    bitmap.union_with(&(0..60000u32).filter(|i| i % 2 == 1).collect());
+    assert_eq!(bitmap.run_optimize(), false);
+    assert_eq!(bitmap.remove_run_compression(), true);
+}
+
+#[test]
+fn iter() {
+    let mut bitmap: RoaringBitmap<u32> = (0..65536u32).collect();
+    bitmap.remove(10);
+    assert_eq!(bitmap.iter().take(12).collect::<Vec<u32>>(), vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 11, 12]);
+    assert_eq!(bitmap.iter().count(), 65535);
+}
+
+#[test]
+fn run_array() {
+    let run: RoaringBitmap<u32> = (0..65536u32).filter(|&i| i != 3000).collect();
+    let array: RoaringBitmap<u32> = (2000..4000u32).collect();
+    let outside: RoaringBitmap<u32> = (100000..102000u32).collect();
+
+    assert_eq!(run.is_disjoint(&array), false);
+    assert_eq!(run.is_disjoint(&outside), true);
+    assert_eq!(array.is_subset(&run), false);
+    assert_eq!((&array - &(3000..3001u32).collect()).is_subset(&run), true);
+
+    assert_eq!(&run | &array, (0..65536u32).collect());
+    assert_eq!(&array | &run, (0..65536u32).collect());
+    assert_eq!(&run & &array, (2000..4000u32).filter(|&i| i != 3000).collect());
+    assert_eq!(&array & &run, (2000..4000u32).filter(|&i| i != 3000).collect());
+    assert_eq!(&run - &array, (0..2000u32).chain(4000..65536).collect());
+    assert_eq!(&array - &run, (3000..3001u32).collect());
+    assert_eq!(&run ^ &array, (0..2000u32).chain(3000..3001).chain(4000..65536).collect());
+    assert_eq!(&array ^ &run, (0..2000u32).chain(3000..3001).chain(4000..65536).collect());
+}
+
+#[test]
+fn run_bitmap() {
+    let run: RoaringBitmap<u32> = (0..65536u32).filter(|&i| i != 30000).collect();
+    let bitmap: RoaringBitmap<u32> = (20000..40000u32).collect();
+
+    assert_eq!(run.is_disjoint(&bitmap), false);
+    assert_eq!(bitmap.is_subset(&run), false);
+
+    assert_eq!(&run | &bitmap, (0..65536u32).collect());
+    assert_eq!(&bitmap | &run, (0..65536u32).collect());
+    assert_eq!(&run & &bitmap, (20000..40000u32).filter(|&i| i != 30000).collect());
+    assert_eq!(&bitmap & &run, (20000..40000u32).filter(|&i| i != 30000).collect());
+    assert_eq!(&run - &bitmap, (0..20000u32).chain(40000..65536).collect());
+    assert_eq!(&bitmap - &run, (30000..30001u32).collect());
+    assert_eq!(&run ^ &bitmap, (0..20000u32).chain(30000..30001).chain(40000..65536).collect());
+    assert_eq!(&bitmap ^ &run, (0..20000u32).chain(30000..30001).chain(40000..65536).collect());
+}
+
+#[test]
+fn run_run() {
+    let run1: RoaringBitmap<u32> = (0..65536u32).filter(|&i| i != 100).collect();
+    let run2: RoaringBitmap<u32> = (0..65536u32).filter(|&i| i != 200).collect();
+
+    assert_eq!(run1.is_subset(&run2), false);
+    assert_eq!(run1.is_disjoint(&run2), false);
+
+    assert_eq!(&run1 | &run2, (0..65536u32).collect());
+    assert_eq!(&run1 & &run2, (0..65536u32).filter(|&i| i != 100 && i != 200).collect());
+    assert_eq!(&run1 - &run2, (200..201u32).collect());
+    assert_eq!(&run1 ^ &run2, (100..101u32).chain(200..201).collect());
+}
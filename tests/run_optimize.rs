@@ -0,0 +1,72 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+#[test]
+fn array() {
+    let mut bitmap: RoaringBitmap<u32> = (0..2000u32).filter(|i| i % 2 == 0).collect();
+    let original = bitmap.clone();
+    assert_eq!(bitmap.run_optimize(), false);
+    assert_eq!(bitmap.remove_run_compression(), false);
+    assert_eq!(bitmap, original);
+}
+
+#[test]
+fn array_to_run() {
+    let mut bitmap: RoaringBitmap<u32> = (0..2000u32).collect();
+    assert_eq!(bitmap.run_optimize(), true);
+    assert_eq!(bitmap.run_optimize(), false);
+    assert_eq!(bitmap, (0..2000u32).collect());
+    assert_eq!(bitmap.remove_run_compression(), true);
+    assert_eq!(bitmap.remove_run_compression(), false);
+    assert_eq!(bitmap, (0..2000u32).collect());
+}
+
+#[test]
+fn bitmap() {
+    let mut bitmap: RoaringBitmap<u32> = (0..20000u32).filter(|i| i % 3 != 0).collect();
+    let original = bitmap.clone();
+    assert_eq!(bitmap.run_optimize(), false);
+    assert_eq!(bitmap, original);
+}
+
+#[test]
+fn bitmap_to_run() {
+    let mut bitmap: RoaringBitmap<u32> = (0..20000u32).chain(30000..50000).collect();
+    assert_eq!(bitmap.remove_run_compression(), true);
+    assert_eq!(bitmap.run_optimize(), true);
+    assert_eq!(bitmap.len(), 40000);
+    assert_eq!(bitmap.contains(25000), false);
+    assert_eq!(bitmap.contains(49999), true);
+    assert_eq!(bitmap.remove_run_compression(), true);
+    assert_eq!(bitmap, (0..20000u32).chain(30000..50000).collect());
+}
+
+#[test]
+fn run_to_array() {
+    let mut bitmap: RoaringBitmap<u32> = (0..65536u32).collect();
+    for i in 10..65536u32 {
+        bitmap.remove(i);
+    }
+    for i in (20..36u32).filter(|i| i % 2 == 0) {
+        bitmap.insert(i);
+    }
+    assert_eq!(bitmap.run_optimize(), true);
+    assert_eq!(bitmap, (0..10u32).chain((20..36).filter(|i| i % 2 == 0)).collect());
+}
+
+#[test]
+fn mixed() {
+    let mut bitmap: RoaringBitmap<u32> = (0..100u32).map(|i| i * 7)
+        .chain(100_000..180_000)
+        .chain((200_000..250_000).filter(|i| i % 5 != 0))
+        .collect();
+    assert_eq!(bitmap.remove_run_compression(), true);
+    let original = bitmap.clone();
+    assert_eq!(bitmap.run_optimize(), true);
+    assert_eq!(bitmap, original);
+    assert_eq!(bitmap.iter().collect::<Vec<u32>>(), original.iter().collect::<Vec<u32>>());
+    bitmap.union_with(&(180_000..190_000u32).collect());
+    assert_eq!(bitmap.len(), original.len() + 10_000);
+    assert_eq!(bitmap.remove_run_compression(), true);
+    assert_eq!(bitmap.remove_run_compression(), false);
+}
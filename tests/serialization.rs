@@ -0,0 +1,107 @@
+extern crate roaring;
+
+use roaring::{ RoaringBitmap, RoaringTreemap, DeserializeError };
+
+// Produced by the Java and C implementations, see
+// https://github.com/RoaringBitmap/RoaringFormatSpec/tree/master/testdata
+static BITMAP_WITHOUT_RUNS: &'static [u8] = include_bytes!("bitmapwithoutruns.bin");
+static BITMAP_WITH_RUNS: &'static [u8] = include_bytes!("bitmapwithruns.bin");
+
+fn test_data_bitmap() -> RoaringBitmap<u32> {
+    (0..100u32).map(|i| i * 1000)
+        .chain((100_000..200_000u32).map(|i| i * 3))
+        .chain(700_000..800_000u32)
+        .collect()
+}
+
+fn serialize(bitmap: &RoaringBitmap<u32>) -> Vec<u8> {
+    let mut bytes = vec![];
+    bitmap.serialize_into(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), bitmap.serialized_size());
+    bytes
+}
+
+#[test]
+fn deserialize_without_runs() {
+    assert_eq!(RoaringBitmap::deserialize_from(BITMAP_WITHOUT_RUNS).unwrap(), test_data_bitmap());
+}
+
+#[test]
+fn deserialize_with_runs() {
+    assert_eq!(RoaringBitmap::deserialize_from(BITMAP_WITH_RUNS).unwrap(), test_data_bitmap());
+}
+
+#[test]
+fn serialize_without_runs() {
+    let mut bitmap = test_data_bitmap();
+    bitmap.remove_run_compression();
+    assert_eq!(&serialize(&bitmap)[..], BITMAP_WITHOUT_RUNS);
+}
+
+#[test]
+fn serialize_with_runs() {
+    let mut bitmap = test_data_bitmap();
+    bitmap.run_optimize();
+    assert_eq!(&serialize(&bitmap)[..], BITMAP_WITH_RUNS);
+}
+
+#[test]
+fn roundtrip() {
+    let bitmaps: Vec<RoaringBitmap<u32>> = vec![
+        RoaringBitmap::new(),
+        (0..2000u32).collect(),
+        (0..6000u32).chain(1_000_000..1_012_000).collect(),
+        (0..65536u32).chain(200_000..200_010).collect(),
+        (0..5u32).map(|i| i * 65536).collect(),
+    ];
+    for bitmap in bitmaps {
+        assert_eq!(RoaringBitmap::deserialize_from(&serialize(&bitmap)[..]).unwrap(), bitmap);
+    }
+}
+
+#[test]
+fn truncated() {
+    for data in &[BITMAP_WITHOUT_RUNS, BITMAP_WITH_RUNS] {
+        for &len in &[0, 3, 7, 100, data.len() / 2, data.len() - 1] {
+            match RoaringBitmap::deserialize_from(&data[..len]) {
+                Err(DeserializeError::UnexpectedEnd) => (),
+                other => panic!("{:?}", other),
+            }
+        }
+    }
+}
+
+#[test]
+fn invalid() {
+    match RoaringBitmap::deserialize_from(&[1u8, 2, 3, 4][..]) {
+        Err(DeserializeError::InvalidCookie(0x04030201)) => (),
+        other => panic!("{:?}", other),
+    }
+
+    // Two array containers, both with key 0.
+    let mut bytes = serialize(&(0..2u32).chain(65536..65538).collect());
+    bytes[12] = 0;
+    match RoaringBitmap::deserialize_from(&bytes[..]) {
+        Err(DeserializeError::UnsortedKeys) => (),
+        other => panic!("{:?}", other),
+    }
+
+    // An array container claiming values [5, 5].
+    let mut bytes = serialize(&(5..7u32).collect());
+    let len = bytes.len();
+    bytes[len - 2] = 5;
+    match RoaringBitmap::deserialize_from(&bytes[..]) {
+        Err(DeserializeError::UnsortedValues) => (),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn treemap() {
+    let treemap: RoaringTreemap = (0..100u64).chain((1 << 40)..(1 << 40) + 70000).chain(u64::max_value() - 10..u64::max_value()).collect();
+    let mut bytes = vec![];
+    treemap.serialize_into(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), treemap.serialized_size());
+    assert_eq!(RoaringTreemap::deserialize_from(&bytes[..]).unwrap(), treemap);
+    assert!(RoaringTreemap::deserialize_from(&bytes[..bytes.len() - 1]).is_err());
+}
@@ -0,0 +1,92 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+#[test]
+fn array_and_array() {
+    let bitmap1: RoaringBitmap<u32> = (0..2000u32).map(|i| i * 2).collect();
+    let bitmap2: RoaringBitmap<u32> = (0..1000u32).map(|i| i * 3).collect();
+
+    assert_eq!(bitmap1.union(&bitmap2).collect::<Vec<u32>>(), (0..4000u32).filter(|i| i % 2 == 0 || (i % 3 == 0 && *i < 3000)).collect::<Vec<u32>>());
+    assert_eq!(bitmap1.intersection(&bitmap2).collect::<Vec<u32>>(), (0..3000u32).filter(|i| i % 6 == 0).collect::<Vec<u32>>());
+    assert_eq!(bitmap1.difference(&bitmap2).collect::<Vec<u32>>(), (0..4000u32).filter(|i| i % 2 == 0 && (i % 3 != 0 || *i >= 3000)).collect::<Vec<u32>>());
+    assert_eq!(bitmap1.symmetric_difference(&bitmap2).collect::<Vec<u32>>(), (0..4000u32).filter(|i| (i % 2 == 0) != (i % 3 == 0 && *i < 3000)).collect::<Vec<u32>>());
+}
+
+#[test]
+fn array_and_bitmap() {
+    let bitmap1: RoaringBitmap<u32> = (0..3000u32).map(|i| i * 3).collect();
+    let bitmap2: RoaringBitmap<u32> = (0..20000u32).filter(|i| i % 5 != 0).collect();
+
+    assert_eq!(bitmap1.union(&bitmap2).collect::<Vec<u32>>(), (0..20000u32).filter(|i| i % 5 != 0 || (i % 3 == 0 && *i < 9000)).collect::<Vec<u32>>());
+    assert_eq!(bitmap1.intersection(&bitmap2).collect::<Vec<u32>>(), (0..9000u32).filter(|i| i % 3 == 0 && i % 5 != 0).collect::<Vec<u32>>());
+    assert_eq!(bitmap2.difference(&bitmap1).collect::<Vec<u32>>(), (0..20000u32).filter(|i| i % 5 != 0 && (i % 3 != 0 || *i >= 9000)).collect::<Vec<u32>>());
+    assert_eq!(bitmap1.symmetric_difference(&bitmap2).collect::<Vec<u32>>(), (0..20000u32).filter(|i| (i % 5 != 0) != (i % 3 == 0 && *i < 9000)).collect::<Vec<u32>>());
+}
+
+#[test]
+fn bitmap_and_run() {
+    let bitmap1: RoaringBitmap<u32> = (0..60000u32).filter(|i| i % 2 == 0).collect();
+    let mut bitmap2: RoaringBitmap<u32> = RoaringBitmap::new();
+    bitmap2.insert_range(1000..3000);
+    bitmap2.insert_range(40000..50000);
+    bitmap2.run_optimize();
+    let in_runs = |i: u32| (i >= 1000 && i < 3000) || (i >= 40000 && i < 50000);
+
+    assert_eq!(bitmap1.union(&bitmap2).collect::<Vec<u32>>(), (0..60000u32).filter(|&i| i % 2 == 0 || in_runs(i)).collect::<Vec<u32>>());
+    assert_eq!(bitmap1.intersection(&bitmap2).collect::<Vec<u32>>(), (0..60000u32).filter(|&i| i % 2 == 0 && in_runs(i)).collect::<Vec<u32>>());
+    assert_eq!(bitmap1.difference(&bitmap2).collect::<Vec<u32>>(), (0..60000u32).filter(|&i| i % 2 == 0 && !in_runs(i)).collect::<Vec<u32>>());
+    assert_eq!(bitmap2.difference(&bitmap1).collect::<Vec<u32>>(), (0..60000u32).filter(|&i| i % 2 == 1 && in_runs(i)).collect::<Vec<u32>>());
+    assert_eq!(bitmap1.symmetric_difference(&bitmap2).collect::<Vec<u32>>(), (0..60000u32).filter(|&i| (i % 2 == 0) != in_runs(i)).collect::<Vec<u32>>());
+}
+
+#[test]
+fn run_and_run_and_array() {
+    let mut bitmap1: RoaringBitmap<u32> = RoaringBitmap::new();
+    bitmap1.insert_range(1000..3000);
+    bitmap1.run_optimize();
+    let mut bitmap2: RoaringBitmap<u32> = RoaringBitmap::new();
+    bitmap2.insert_range(2000..4000);
+    bitmap2.run_optimize();
+    let array: RoaringBitmap<u32> = vec![5u32, 1500, 2999, 3000].into_iter().collect();
+
+    assert_eq!(bitmap1.union(&bitmap2).collect::<Vec<u32>>(), (1000..4000u32).collect::<Vec<u32>>());
+    assert_eq!(bitmap1.intersection(&bitmap2).collect::<Vec<u32>>(), (2000..3000u32).collect::<Vec<u32>>());
+    assert_eq!(bitmap1.difference(&bitmap2).collect::<Vec<u32>>(), (1000..2000u32).collect::<Vec<u32>>());
+    assert_eq!(bitmap1.symmetric_difference(&bitmap2).collect::<Vec<u32>>(), (1000..2000u32).chain(3000..4000).collect::<Vec<u32>>());
+
+    assert_eq!(array.union(&bitmap1).collect::<Vec<u32>>(), vec![5u32].into_iter().chain(1000..3001).collect::<Vec<u32>>());
+    assert_eq!(array.intersection(&bitmap1).collect::<Vec<u32>>(), vec![1500, 2999]);
+    assert_eq!(array.difference(&bitmap1).collect::<Vec<u32>>(), vec![5, 3000]);
+    assert_eq!(bitmap1.difference(&array).collect::<Vec<u32>>(), (1000..2999u32).filter(|&i| i != 1500).collect::<Vec<u32>>());
+    assert_eq!(array.symmetric_difference(&bitmap1).collect::<Vec<u32>>(), vec![5u32].into_iter().chain((1000..2999).filter(|&i| i != 1500)).chain(vec![3000]).collect::<Vec<u32>>());
+}
+
+#[test]
+fn with_empty() {
+    let bitmap: RoaringBitmap<u32> = (0..3000u32).map(|i| i * 3).collect();
+    let empty: RoaringBitmap<u32> = RoaringBitmap::new();
+
+    assert_eq!(bitmap.union(&empty).collect::<Vec<u32>>(), bitmap.iter().collect::<Vec<u32>>());
+    assert_eq!(bitmap.intersection(&empty).next(), None);
+    assert_eq!(bitmap.difference(&empty).collect::<Vec<u32>>(), bitmap.iter().collect::<Vec<u32>>());
+    assert_eq!(empty.difference(&bitmap).next(), None);
+    assert_eq!(empty.symmetric_difference(&bitmap).collect::<Vec<u32>>(), bitmap.iter().collect::<Vec<u32>>());
+}
+
+#[test]
+fn other_ends_first() {
+    let bitmap1: RoaringBitmap<u32> = vec![1u32, 5, 100000].into_iter().collect();
+    let bitmap2: RoaringBitmap<u32> = vec![1u32].into_iter().collect();
+    assert_eq!(bitmap1.difference(&bitmap2).collect::<Vec<u32>>(), vec![5, 100000]);
+    assert_eq!(bitmap1.symmetric_difference(&bitmap2).collect::<Vec<u32>>(), vec![5, 100000]);
+    assert_eq!(bitmap2.symmetric_difference(&bitmap1).collect::<Vec<u32>>(), vec![5, 100000]);
+}
+
+#[test]
+fn disjoint_keys() {
+    let bitmap1: RoaringBitmap<u32> = (0..10000u32).collect();
+    let bitmap2: RoaringBitmap<u32> = (100000..110000u32).collect();
+    assert_eq!(bitmap1.intersection(&bitmap2).next(), None);
+    assert_eq!(bitmap1.difference(&bitmap2).count(), 10000);
+    assert_eq!(bitmap1.union(&bitmap2).count(), 20000);
+}
@@ -0,0 +1,46 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+fn check(bitmap1: &RoaringBitmap<u32>, bitmap2: &RoaringBitmap<u32>) {
+    assert_eq!(bitmap1.intersection_len(bitmap2), (bitmap1 & bitmap2).len());
+    assert_eq!(bitmap1.union_len(bitmap2), (bitmap1 | bitmap2).len());
+    assert_eq!(bitmap1.difference_len(bitmap2), (bitmap1 - bitmap2).len());
+    assert_eq!(bitmap1.symmetric_difference_len(bitmap2), (bitmap1 ^ bitmap2).len());
+    assert_eq!(bitmap1.intersects(bitmap2), !(bitmap1 & bitmap2).is_empty());
+}
+
+fn stores() -> Vec<RoaringBitmap<u32>> {
+    let mut run: RoaringBitmap<u32> = RoaringBitmap::new();
+    run.insert_range(1000..3000);
+    run.insert_range(40000..50000);
+    run.insert_range(200000..200100);
+    run.run_optimize();
+    vec![
+        RoaringBitmap::new(),
+        (0..3000u32).map(|i| i * 3).collect(),
+        (0..60000u32).filter(|i| i % 5 != 0).collect(),
+        (100000..300000u32).filter(|i| i % 2 == 0).collect(),
+        run,
+    ]
+}
+
+#[test]
+fn matches_materialised() {
+    let stores = stores();
+    for bitmap1 in &stores {
+        for bitmap2 in &stores {
+            check(bitmap1, bitmap2);
+        }
+    }
+}
+
+#[test]
+fn jaccard_index() {
+    let bitmap1: RoaringBitmap<u32> = (0..100u32).collect();
+    let bitmap2: RoaringBitmap<u32> = (50..150u32).collect();
+    let empty: RoaringBitmap<u32> = RoaringBitmap::new();
+    assert_eq!(bitmap1.jaccard_index(&bitmap1), 1.0);
+    assert_eq!(bitmap1.jaccard_index(&bitmap2), 50.0 / 150.0);
+    assert_eq!(bitmap1.jaccard_index(&empty), 0.0);
+    assert!(empty.jaccard_index(&empty).is_nan());
+}
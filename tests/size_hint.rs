@@ -51,3 +51,17 @@ fn bitmaps() {
 
     assert_eq!((0, Some(0)), bitmap.iter().skip(28000).size_hint());
 }
+
+#[test]
+fn exact() {
+    let mut bitmap: RoaringBitmap<u32> = (0..6000u32).chain(1000000..1002000u32).collect();
+    bitmap.insert_range(2000000..2100000);
+    let mut iter = bitmap.iter();
+    assert_eq!(iter.len(), 108000);
+    for remaining in (0..108000usize).rev() {
+        iter.next();
+        assert_eq!((remaining, Some(remaining)), iter.size_hint());
+    }
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.len(), 0);
+}
@@ -0,0 +1,78 @@
+extern crate roaring;
+use roaring::RoaringBitmap;
+
+// A bitmap with array, bitmap and run containers, and gaps between them.
+fn bitmap() -> RoaringBitmap<u32> {
+    let mut bitmap: RoaringBitmap<u32> = (0..2000u32).map(|i| i * 7).collect();
+    bitmap.extend((131072..196608u32).filter(|i| i % 3 != 0));
+    bitmap.insert_range(300000..420000);
+    bitmap.run_optimize();
+    bitmap
+}
+
+#[test]
+fn split_off() {
+    for &value in &[0, 1, 7, 5000, 65536, 131072, 150000, 150001, 250000, 300000, 350000, 419999, 420000, 500000] {
+        let mut lower = bitmap();
+        let upper = lower.split_off(&value);
+        let all = bitmap().iter().collect::<Vec<u32>>();
+        assert_eq!(lower.iter().collect::<Vec<u32>>(), all.iter().cloned().filter(|&v| v < value).collect::<Vec<u32>>(), "{}", value);
+        assert_eq!(upper.iter().collect::<Vec<u32>>(), all.iter().cloned().filter(|&v| v >= value).collect::<Vec<u32>>(), "{}", value);
+        assert_eq!(lower.len() + upper.len(), all.len() as u64);
+    }
+}
+
+#[test]
+fn split_off_empty() {
+    let mut bitmap = RoaringBitmap::<u32>::new();
+    assert_eq!(bitmap.split_off(&10).is_empty(), true);
+    assert_eq!(bitmap.is_empty(), true);
+}
+
+#[test]
+fn split_off_full_container() {
+    let mut lower = RoaringBitmap::<u16>::new();
+    lower.insert_range(..);
+    let upper = lower.split_off(&1000);
+    assert_eq!(lower, (0..1000u16).collect());
+    assert_eq!(upper.len(), 65536 - 1000);
+}
+
+#[test]
+fn append_disjoint() {
+    let mut lower = bitmap();
+    let mut upper = lower.split_off(&150000);
+    lower.append(&mut upper);
+    assert_eq!(lower, bitmap());
+    assert_eq!(upper.is_empty(), true);
+}
+
+#[test]
+fn append_before() {
+    let mut upper = bitmap();
+    let mut lower = upper.split_off(&150000);
+    upper.append(&mut lower);
+    assert_eq!(upper, bitmap());
+    assert_eq!(lower.is_empty(), true);
+}
+
+#[test]
+fn append_overlapping() {
+    let mut rb1: RoaringBitmap<u32> = (0..200000u32).filter(|i| i % 2 == 0).collect();
+    let mut rb2: RoaringBitmap<u32> = (100000..300000u32).filter(|i| i % 3 == 0).collect();
+    let expected = &rb1 | &rb2;
+    rb1.append(&mut rb2);
+    assert_eq!(rb1, expected);
+    assert_eq!(rb2.is_empty(), true);
+}
+
+#[test]
+fn append_empty() {
+    let mut rb1 = bitmap();
+    let mut rb2 = RoaringBitmap::new();
+    rb1.append(&mut rb2);
+    assert_eq!(rb1, bitmap());
+    rb2.append(&mut rb1);
+    assert_eq!(rb2, bitmap());
+    assert_eq!(rb1.is_empty(), true);
+}
@@ -0,0 +1,138 @@
+extern crate roaring;
+
+use std::{ u32, u64 };
+
+use roaring::RoaringTreemap;
+
+#[test]
+fn smoke() {
+    let mut treemap = RoaringTreemap::new();
+    assert_eq!(treemap.len(), 0);
+    assert_eq!(treemap.is_empty(), true);
+    treemap.remove(0);
+    assert_eq!(treemap.len(), 0);
+    treemap.insert(1);
+    assert_eq!(treemap.contains(1), true);
+    assert_eq!(treemap.len(), 1);
+    treemap.insert(u64::MAX - 2);
+    assert_eq!(treemap.contains(u64::MAX - 2), true);
+    treemap.insert(u64::MAX);
+    assert_eq!(treemap.contains(u64::MAX), true);
+    treemap.insert(u32::MAX as u64 + 1);
+    assert_eq!(treemap.contains(u32::MAX as u64 + 1), true);
+    assert_eq!(treemap.len(), 4);
+    treemap.remove(u64::MAX);
+    assert_eq!(treemap.contains(u64::MAX), false);
+    assert_eq!(treemap.contains(u64::MAX - 1), false);
+    assert_eq!(treemap.contains(u32::MAX as u64), false);
+    assert_eq!(treemap.len(), 3);
+}
+
+#[test]
+fn iter() {
+    let values = vec![0, 5, 1 << 32, (1 << 32) + 7, 1 << 40, u64::MAX];
+    let treemap: RoaringTreemap = values.iter().collect();
+    assert_eq!(treemap.iter().collect::<Vec<u64>>(), values);
+    assert_eq!(treemap.iter().size_hint(), (6, Some(6)));
+    assert_eq!(treemap.clone().into_iter().collect::<Vec<u64>>(), values);
+}
+
+#[test]
+fn dense() {
+    let treemap: RoaringTreemap = (0..3 * 65536u64).map(|i| (1 << 33) + i).collect();
+    assert_eq!(treemap.len(), 3 * 65536);
+    assert_eq!(treemap.contains((1 << 33) + 65536), true);
+    assert_eq!(treemap.contains(1 << 32), false);
+}
+
+#[test]
+fn ops() {
+    let rt1: RoaringTreemap = (0..4u64).chain((1 << 40)..(1 << 40) + 4).collect();
+    let rt2: RoaringTreemap = (2..6u64).chain((1 << 50)..(1 << 50) + 4).collect();
+
+    assert_eq!(rt1.is_disjoint(&rt2), false);
+    assert_eq!(rt1.is_subset(&rt2), false);
+    assert_eq!((&rt1 & &rt2).is_subset(&rt1), true);
+
+    assert_eq!(&rt1 | &rt2, (0..6u64).chain((1 << 40)..(1 << 40) + 4).chain((1 << 50)..(1 << 50) + 4).collect());
+    assert_eq!(&rt1 & &rt2, (2..4u64).collect());
+    assert_eq!(&rt1 - &rt2, (0..2u64).chain((1 << 40)..(1 << 40) + 4).collect());
+    assert_eq!(&rt1 ^ &rt2, (0..2u64).chain(4..6).chain((1 << 40)..(1 << 40) + 4).chain((1 << 50)..(1 << 50) + 4).collect());
+}
+
+#[test]
+fn set_op_iters() {
+    let rt1: RoaringTreemap = (0..4u64).chain((1 << 40)..(1 << 40) + 4).collect();
+    let rt2: RoaringTreemap = (2..6u64).chain((1 << 50)..(1 << 50) + 4).collect();
+
+    assert_eq!(rt1.union(&rt2).collect::<RoaringTreemap>(), &rt1 | &rt2);
+    assert_eq!(rt1.intersection(&rt2).collect::<Vec<u64>>(), vec![2, 3]);
+    assert_eq!(rt1.difference(&rt2).collect::<RoaringTreemap>(), &rt1 - &rt2);
+    assert_eq!(rt2.difference(&rt1).collect::<RoaringTreemap>(), &rt2 - &rt1);
+    assert_eq!(rt1.symmetric_difference(&rt2).collect::<RoaringTreemap>(), &rt1 ^ &rt2);
+    assert_eq!(rt1.union(&RoaringTreemap::new()).collect::<Vec<u64>>(), rt1.iter().collect::<Vec<u64>>());
+    assert_eq!(rt1.intersection(&RoaringTreemap::new()).next(), None);
+}
+
+#[test]
+fn min_max() {
+    let mut treemap = RoaringTreemap::new();
+    assert_eq!(treemap.min(), None);
+    assert_eq!(treemap.max(), None);
+    treemap.insert(1 << 40);
+    assert_eq!(treemap.min(), Some(1 << 40));
+    assert_eq!(treemap.max(), Some(1 << 40));
+    treemap.insert(7);
+    treemap.insert(u64::MAX);
+    assert_eq!(treemap.min(), Some(7));
+    assert_eq!(treemap.max(), Some(u64::MAX));
+}
+
+#[test]
+fn ranges() {
+    let mut treemap = RoaringTreemap::new();
+    assert_eq!(treemap.insert_range(5..5), 0);
+    assert_eq!(treemap.insert_range((1 << 32) - 10..(2 << 32) + 10), (1 << 32) + 20);
+    assert_eq!(treemap.len(), (1 << 32) + 20);
+    assert_eq!(treemap.contains((1 << 32) - 11), false);
+    assert_eq!(treemap.contains((2 << 32) + 9), true);
+    assert_eq!(treemap.insert_range(u64::MAX - 1..), 2);
+    assert_eq!(treemap.max(), Some(u64::MAX));
+
+    assert_eq!(treemap.remove_range((1 << 32) - 5..=(2 << 32) + 4), (1 << 32) + 10);
+    assert_eq!(treemap.len(), 12);
+    assert_eq!(treemap.remove_range(..=u64::MAX - 1), 11);
+    assert_eq!(treemap.iter().collect::<Vec<u64>>(), vec![u64::MAX]);
+    assert_eq!(treemap.remove_range(..), 1);
+    assert_eq!(treemap.is_empty(), true);
+}
+
+#[test]
+fn into_iter() {
+    let values = vec![0, 5, 1 << 32, (1 << 32) + 7, 1 << 40, u64::MAX];
+    let treemap: RoaringTreemap = values.iter().collect();
+    let mut iter = treemap.into_iter();
+    assert_eq!(iter.size_hint(), (6, Some(6)));
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.size_hint(), (5, Some(5)));
+    assert_eq!(iter.collect::<Vec<u64>>(), &values[1..]);
+}
+
+#[test]
+fn raw64() {
+    let treemap: RoaringTreemap = (0..100u64).chain((1 << 40)..(1 << 40) + 70000).chain(u64::MAX - 10..u64::MAX).collect();
+    assert_eq!(RoaringTreemap::from_raw64(treemap.to_raw64()), treemap);
+    assert_eq!(RoaringTreemap::from_raw64(RoaringTreemap::new().to_raw64()), RoaringTreemap::new());
+}
+
+#[test]
+fn raw64_invalid() {
+    let treemap: RoaringTreemap = (0..100u64).chain((1 << 40)..(1 << 40) + 10).collect();
+    let raw64 = treemap.to_raw64();
+    for len in 0..raw64.len() {
+        assert!(RoaringTreemap::try_from_raw64(&raw64[..len]).is_err());
+    }
+    assert!(RoaringTreemap::try_from_raw64(&[1, 1 << 32, 0]).is_err());
+    assert!(RoaringTreemap::try_from_raw64(&[1, 5, 0]).is_err());
+    assert!(RoaringTreemap::try_from_raw64(&[2, 5, 0, 5, 0]).is_err());
+}